@@ -11,7 +11,7 @@ pub struct Transition {
     duration: Duration,
     #[educe(Default = Duration::ZERO)]
     delay: Duration,
-    #[educe(Default = cubic_out)]
+    #[educe(Default = Easing::from(cubic_out))]
     easing: Easing,
     #[educe(Default = true)]
     enabled: bool,
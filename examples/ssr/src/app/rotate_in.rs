@@ -2,9 +2,8 @@ use std::time::Duration;
 
 use leptos::web_sys::HtmlElement;
 use leptos_animate::{
-    animation::{listeners, Animation as AnimationTrait, Initializer},
+    animation::{Animation, Initializer},
     easing::elastic_out,
-    impl_empty_animation_listeners,
     utils::spawn_animation,
 };
 
@@ -12,34 +11,19 @@ use leptos_animate::{
 pub struct RotateIn;
 
 impl Initializer for RotateIn {
-    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
-        Animation { element }
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        RotateInAnimation { element }
     }
 }
 
-struct Animation {
+#[derive(Animation)]
+#[animation(listens(before_enter))]
+struct RotateInAnimation {
     element: HtmlElement,
 }
 
-impl_empty_animation_listeners!(
-    Animation;
-    Enter,
-    ImmediateEffect,
-    Effect,
-    Mutation,
-    ParentMutation,
-    EnterAnimationsFinished,
-    MutationAnimationsFinished,
-    ParentMutationAnimationsFinished,
-    Cleanup
-);
-
-impl listeners::BeforeEnter for Animation {
-    fn listening_for_before_enter(&self) -> bool {
-        true
-    }
-
-    fn on_before_enter(&mut self) {
+impl RotateInAnimation {
+    fn before_enter(&mut self) {
         spawn_animation()
             .element(&self.element)
             .keyframe(|t| {
@@ -56,5 +40,3 @@ impl listeners::BeforeEnter for Animation {
             .call();
     }
 }
-
-impl AnimationTrait for Animation {}
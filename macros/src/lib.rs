@@ -0,0 +1,430 @@
+//! Derive macros for `leptos_animate`. Use them through the re-exports of the
+//! main crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, parse_macro_input, DeriveInput, Ident};
+
+struct Extra {
+    name: &'static str,
+    method: &'static str,
+    output: &'static str,
+}
+
+struct Listener {
+    name: &'static str,
+    trait_name: &'static str,
+    params: &'static [(&'static str, &'static str)],
+    extras: &'static [Extra],
+}
+
+const MUTATIONS: &[(&str, &str)] = &[(
+    "mutations",
+    "&[::leptos_animate::__private::MutationRecord]",
+)];
+
+const LISTENERS: &[Listener] = &[
+    Listener {
+        name: "before_enter",
+        trait_name: "BeforeEnter",
+        params: &[],
+        extras: &[],
+    },
+    Listener {
+        name: "enter",
+        trait_name: "Enter",
+        params: &[],
+        extras: &[Extra {
+            name: "delay",
+            method: "enter_delay",
+            output: "::leptos_animate::__private::Duration",
+        }],
+    },
+    Listener {
+        name: "immediate_effect",
+        trait_name: "ImmediateEffect",
+        params: &[],
+        extras: &[],
+    },
+    Listener {
+        name: "effect",
+        trait_name: "Effect",
+        params: &[],
+        extras: &[Extra {
+            name: "delay",
+            method: "effect_delay",
+            output: "::leptos_animate::__private::Duration",
+        }],
+    },
+    Listener {
+        name: "mutation",
+        trait_name: "Mutation",
+        params: MUTATIONS,
+        extras: &[],
+    },
+    Listener {
+        name: "parent_mutation",
+        trait_name: "ParentMutation",
+        params: MUTATIONS,
+        extras: &[],
+    },
+    Listener {
+        name: "visibility",
+        trait_name: "Visibility",
+        params: &[(
+            "entry",
+            "&::leptos_animate::__private::IntersectionObserverEntry",
+        )],
+        extras: &[
+            Extra {
+                name: "threshold",
+                method: "visibility_threshold",
+                output: "f64",
+            },
+            Extra {
+                name: "root_margin",
+                method: "visibility_root_margin",
+                output: "::std::string::String",
+            },
+        ],
+    },
+    Listener {
+        name: "element_resize",
+        trait_name: "ElementResize",
+        params: &[("entry", "&::leptos_animate::__private::ResizeObserverEntry")],
+        extras: &[],
+    },
+    Listener {
+        name: "enter_animations_finished",
+        trait_name: "EnterAnimationsFinished",
+        params: &[],
+        extras: &[],
+    },
+    Listener {
+        name: "mutation_animations_finished",
+        trait_name: "MutationAnimationsFinished",
+        params: &[],
+        extras: &[],
+    },
+    Listener {
+        name: "parent_mutation_animations_finished",
+        trait_name: "ParentMutationAnimationsFinished",
+        params: &[],
+        extras: &[],
+    },
+    Listener {
+        name: "cleanup",
+        trait_name: "Cleanup",
+        params: &[],
+        extras: &[],
+    },
+];
+
+/// Listener along with the inherent methods its methods are forwarded to.
+struct Listened {
+    listener: &'static Listener,
+    target: Ident,
+    extras: Vec<(&'static Extra, Ident)>,
+}
+
+/// Method of the `Animation` trait itself.
+struct Method {
+    name: &'static str,
+    receiver: &'static str,
+    params: &'static [(&'static str, &'static str)],
+    output: &'static str,
+}
+
+const HANDOVER: &[(&str, &str)] =
+    &[("handover", "&mut ::leptos_animate::animation::Handover")];
+
+const METHODS: &[Method] = &[
+    Method {
+        name: "enabled",
+        receiver: "&self",
+        params: &[],
+        output: "bool",
+    },
+    Method {
+        name: "track",
+        receiver: "&self",
+        params: &[],
+        output: "()",
+    },
+    Method {
+        name: "hand_over",
+        receiver: "&self",
+        params: HANDOVER,
+        output: "()",
+    },
+    Method {
+        name: "take_over",
+        receiver: "&mut self",
+        params: HANDOVER,
+        output: "()",
+    },
+];
+
+#[derive(Default)]
+struct Args {
+    listens: Vec<Listened>,
+    methods: Vec<(&'static Method, Ident)>,
+}
+
+fn find_listener(ident: &Ident) -> syn::Result<&'static Listener> {
+    LISTENERS
+        .iter()
+        .find(|listener| ident == listener.name)
+        .ok_or_else(|| {
+            let names = LISTENERS
+                .iter()
+                .map(|listener| listener.name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            syn::Error::new(
+                ident.span(),
+                format!("unknown listener, expected one of: {names}"),
+            )
+        })
+}
+
+/// Whether the name belongs to a method of [`Animation`] or one of the
+/// listener traits. Forwarding to such a name would end up calling the trait
+/// method itself whenever the inherent one is missing.
+fn is_trait_method(name: &Ident) -> bool {
+    let name = name.to_string();
+
+    METHODS.iter().any(|method| name == method.name)
+        || LISTENERS.iter().any(|listener| {
+            name == format!("on_{}", listener.name)
+                || name == format!("listening_for_{}", listener.name)
+                || listener.extras.iter().any(|extra| name == extra.method)
+        })
+}
+
+fn parse_target(meta: &ParseNestedMeta) -> syn::Result<Ident> {
+    let target = meta.value()?.parse::<Ident>()?;
+
+    if is_trait_method(&target) {
+        return Err(syn::Error::new(
+            target.span(),
+            "the inherent method must not share its name with a trait method",
+        ));
+    }
+
+    Ok(target)
+}
+
+fn parse_args(input: &DeriveInput) -> syn::Result<Args> {
+    let mut args = Args::default();
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("animation"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if let Some(method) = METHODS
+                .iter()
+                .find(|method| meta.path.is_ident(method.name))
+            {
+                args.methods.push((method, parse_target(&meta)?));
+                return Ok(());
+            }
+
+            if !meta.path.is_ident("listens") {
+                let names = METHODS
+                    .iter()
+                    .map(|method| format!("`{}`", method.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(meta.error(format!("expected `listens` or one of: {names}")));
+            }
+
+            meta.parse_nested_meta(|meta| {
+                let ident = meta.path.require_ident()?;
+                let listener = find_listener(ident)?;
+
+                if args
+                    .listens
+                    .iter()
+                    .any(|listened| listened.listener.name == listener.name)
+                {
+                    return Err(meta.error("listener specified more than once"));
+                }
+
+                let mut extras = Vec::new();
+                if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    meta.parse_nested_meta(|meta| {
+                        let extra = listener
+                            .extras
+                            .iter()
+                            .find(|extra| meta.path.is_ident(extra.name))
+                            .ok_or_else(|| {
+                                meta.error(format!(
+                                    "`{}` has no such option",
+                                    listener.name
+                                ))
+                            })?;
+                        extras.push((extra, parse_target(&meta)?));
+                        Ok(())
+                    })?;
+                }
+
+                args.listens.push(Listened {
+                    listener,
+                    target: ident.clone(),
+                    extras,
+                });
+                Ok(())
+            })
+        })?;
+    }
+
+    Ok(args)
+}
+
+fn tokens(source: &str) -> TokenStream2 {
+    source.parse().expect("valid tokens")
+}
+
+fn listener_impl(
+    input: &DeriveInput,
+    listener: &Listener,
+    forwarded: Option<&Listened>,
+) -> TokenStream2 {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let trait_name = Ident::new(listener.trait_name, Span::call_site());
+
+    let Some(Listened { target, extras, .. }) = forwarded else {
+        return quote! {
+            impl #impl_generics ::leptos_animate::animation::listeners::#trait_name
+                for #ident #ty_generics #where_clause {}
+        };
+    };
+
+    let handler = format_ident!("on_{}", listener.name);
+    let flag = format_ident!("listening_for_{}", listener.name);
+    let param_names = listener
+        .params
+        .iter()
+        .map(|(name, _)| Ident::new(name, Span::call_site()))
+        .collect::<Vec<_>>();
+    let param_types = listener.params.iter().map(|(_, ty)| tokens(ty));
+
+    let extras = extras.iter().map(|(extra, target)| {
+        let method = Ident::new(extra.method, Span::call_site());
+        let output = tokens(extra.output);
+        quote! {
+            fn #method(&self) -> #output {
+                Self::#target(self)
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::leptos_animate::animation::listeners::#trait_name
+            for #ident #ty_generics #where_clause
+        {
+            fn #handler(&mut self, #(#param_names: #param_types),*) {
+                Self::#target(self, #(#param_names),*);
+            }
+
+            fn #flag(&self) -> bool {
+                true
+            }
+
+            #(#extras)*
+        }
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let args = parse_args(input)?;
+
+    let listener_impls = LISTENERS.iter().map(|listener| {
+        let listened = args
+            .listens
+            .iter()
+            .find(|listened| listened.listener.name == listener.name);
+        listener_impl(input, listener, listened)
+    });
+
+    let methods = args.methods.iter().map(|(method, target)| {
+        let name = Ident::new(method.name, Span::call_site());
+        let receiver = tokens(method.receiver);
+        let param_names = method
+            .params
+            .iter()
+            .map(|(name, _)| Ident::new(name, Span::call_site()))
+            .collect::<Vec<_>>();
+        let param_types = method.params.iter().map(|(_, ty)| tokens(ty));
+        let output = tokens(method.output);
+
+        quote! {
+            fn #name(#receiver, #(#param_names: #param_types),*) -> #output {
+                Self::#target(self, #(#param_names),*)
+            }
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #(#listener_impls)*
+
+        impl #impl_generics ::leptos_animate::animation::Animation
+            for #ident #ty_generics #where_clause
+        {
+            #(#methods)*
+        }
+    })
+}
+
+/// Implements [`Animation`] along with all of its listener traits.
+///
+/// Listeners named in `#[animation(listens(...))]` are forwarded to inherent
+/// methods named after the listener, e.g. `on_enter` calls `enter` and
+/// `on_mutation` calls `mutation`, while the rest is left empty. Additional
+/// methods of the listeners, as well as the methods of [`Animation`] itself,
+/// are forwarded to the inherent methods given explicitly:
+///
+/// - `enter(delay = ...)` - `enter_delay`
+/// - `effect(delay = ...)` - `effect_delay`
+/// - `visibility(threshold = ..., root_margin = ...)` - `visibility_threshold`
+///   and `visibility_root_margin`
+/// - `enabled = ...`
+/// - `track = ...`
+/// - `hand_over = ...`
+/// - `take_over = ...`
+///
+/// The inherent methods cannot share their names with the trait methods, so
+/// a missing one results in a compile error rather than infinite recursion.
+///
+/// ```ignore
+/// #[derive(Animation)]
+/// #[animation(listens(before_enter, effect(delay = stagger)), enabled = is_enabled)]
+/// struct Wiggle {
+///     element: HtmlElement,
+/// }
+///
+/// impl Wiggle {
+///     fn before_enter(&mut self) { /* ... */ }
+///     fn effect(&mut self) { /* ... */ }
+///     fn stagger(&self) -> Duration { /* ... */ }
+///     fn is_enabled(&self) -> bool { /* ... */ }
+/// }
+/// ```
+///
+/// [`Animation`]: https://docs.rs/leptos_animate/latest/leptos_animate/animation/trait.Animation.html
+#[proc_macro_derive(Animation, attributes(animation))]
+pub fn derive_animation(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,22 +1,43 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use initial::Initial;
 use leptos::{
-    prelude::{on_cleanup, Effect, ImmediateEffect, StoredValue},
+    html::ElementType,
+    prelude::{on_cleanup, Effect, ImmediateEffect, NodeRef, StoredValue},
     task::{spawn_local, tick},
 };
 use send_wrapper::SendWrapper;
+use use_intersection_observer::use_intersection_observer;
 use use_observer::use_observer;
+use use_resize_observer::use_resize_observer;
 use wasm_bindgen::JsCast;
 use web_sys::{self, Element, HtmlElement, MutationRecord};
 
 use crate::{
-    animation::{Animation, Ext, Initializer},
+    animation::{Animation, Ext, Handover, Initializer},
+    animation_mode,
+    reduced_motion,
     utils::{animation_frame, log_error, OnAnimationsFinishedExt},
+    AnimationHandle,
 };
 
 mod initial;
+mod observer_registry;
+mod source;
+mod use_intersection_observer;
 mod use_observer;
+mod use_resize_observer;
+
+pub use source::{reactive, AnimationSource, Reactive};
+
+thread_local! {
+    /// Set while the animations of an element are being replaced, so they do
+    /// not mistake it for the element leaving.
+    static SWAPPING: Cell<bool> = const { Cell::new(false) };
+}
 
 fn parent(element: &HtmlElement) -> Option<HtmlElement> {
     element.parent_element()?.dyn_into::<HtmlElement>().ok()
@@ -24,9 +45,18 @@ fn parent(element: &HtmlElement) -> Option<HtmlElement> {
 
 type AnimationCell = Rc<RefCell<dyn Animation>>;
 
+fn enabled(animation: &AnimationCell, element: &HtmlElement) -> bool {
+    animation.borrow().enabled()
+        && !animation_mode::is_off()
+        && !reduced_motion::is_disabled(element)
+}
+
 struct Composer {
     element: HtmlElement,
     animation: AnimationCell,
+    /// Whether the element is entering - swapped animations of an element
+    /// already in place skip the enter phase.
+    entering: bool,
 }
 
 impl Composer {
@@ -63,7 +93,8 @@ impl Composer {
         StoredValue::new(ImmediateEffect::new(move || {
             animation.borrow().track();
 
-            if initial.get() || !animation.borrow().enabled() || !element.is_connected() {
+            if initial.get() || !enabled(&animation, &element) || !element.is_connected()
+            {
                 return;
             }
 
@@ -95,11 +126,47 @@ impl Composer {
         let (element, animation) = self.owned_element_and_animation();
 
         use_observer(&element.clone(), move |mutations| {
-            if !animation.borrow().enabled() || !element.is_connected() {
+            if !enabled(&animation, &element) || !element.is_connected() {
                 return;
             }
 
-            animation.borrow_mut().on_mutation(&mutations);
+            animation.borrow_mut().on_mutation(mutations);
+        });
+    }
+
+    fn setup_visibility(&self) {
+        let (element, animation) = self.owned_element_and_animation();
+        let (thresholds, root_margin) = {
+            let animation = animation.borrow();
+            (
+                animation.visibility_thresholds(),
+                animation.visibility_root_margin(),
+            )
+        };
+
+        use_intersection_observer(
+            &element.clone(),
+            &thresholds,
+            &root_margin,
+            move |entry| {
+                if !enabled(&animation, &element) || !element.is_connected() {
+                    return;
+                }
+
+                animation.borrow_mut().on_visibility(&entry);
+            },
+        );
+    }
+
+    fn setup_element_resize(&self) {
+        let (element, animation) = self.owned_element_and_animation();
+
+        use_resize_observer(&element.clone(), move |entry| {
+            if !enabled(&animation, &element) || !element.is_connected() {
+                return;
+            }
+
+            animation.borrow_mut().on_element_resize(&entry);
         });
     }
 
@@ -119,7 +186,7 @@ impl Composer {
 
     fn observe_parent(
         &self,
-        cb: impl Fn(&HtmlElement, Vec<MutationRecord>) + Clone + 'static,
+        cb: impl Fn(&HtmlElement, &[MutationRecord]) + Clone + 'static,
     ) {
         let element = self.element.clone();
 
@@ -136,14 +203,14 @@ impl Composer {
     }
 
     fn setup_parent_mutation(&self) {
-        let animation = Rc::clone(&self.animation);
+        let (element, animation) = self.owned_element_and_animation();
 
         self.observe_parent(move |parent_element, mutations| {
-            if !animation.borrow().enabled() || !parent_element.is_connected() {
+            if !enabled(&animation, &element) || !parent_element.is_connected() {
                 return;
             }
 
-            animation.borrow_mut().on_parent_mutation(&mutations);
+            animation.borrow_mut().on_parent_mutation(mutations);
         });
     }
 
@@ -164,13 +231,14 @@ impl Composer {
     }
 
     fn compose(self) {
-        if self.animation.borrow().listening_for_before_enter()
-            && self.animation.borrow().enabled()
+        if self.entering
+            && self.animation.borrow().listening_for_before_enter()
+            && enabled(&self.animation, &self.element)
         {
             self.animation.borrow_mut().on_before_enter();
         }
 
-        if self.animation.borrow().listening_for_enter() {
+        if self.entering && self.animation.borrow().listening_for_enter() {
             self.setup_enter();
         }
 
@@ -190,10 +258,19 @@ impl Composer {
             self.setup_parent_mutation();
         }
 
-        if self
-            .animation
-            .borrow()
-            .listening_for_enter_animations_finished()
+        if self.animation.borrow().listening_for_visibility() {
+            self.setup_visibility();
+        }
+
+        if self.animation.borrow().listening_for_element_resize() {
+            self.setup_element_resize();
+        }
+
+        if self.entering
+            && self
+                .animation
+                .borrow()
+                .listening_for_enter_animations_finished()
         {
             self.setup_enter_animations_finished();
         }
@@ -215,8 +292,16 @@ impl Composer {
         }
 
         if self.animation.borrow().listening_for_cleanup() {
+            let element = SendWrapper::new(self.element);
             let animation = SendWrapper::new(self.animation);
             on_cleanup(move || {
+                if SWAPPING.get()
+                    || animation_mode::is_off()
+                    || reduced_motion::is_disabled(&element)
+                {
+                    return;
+                }
+
                 animation.borrow_mut().on_cleanup();
             });
         }
@@ -234,18 +319,87 @@ impl Composer {
 /// }
 /// ```
 ///
-/// where `SomeAnimation` must implement [`Initializer`] trait.
+/// where `SomeAnimation` must implement [`Initializer`] trait, or be wrapped
+/// in [`reactive`] to swap the animations whenever it changes.
 ///
 /// The directive runs when the element is created or hydrated.
-pub fn animate(element: Element, animation_initializer: impl Initializer) {
+pub fn animate(element: Element, source: impl AnimationSource) {
     let Ok(element) = element.dyn_into::<HtmlElement>() else {
         log_error!("Could not animate a non-HtmlElement");
         return;
     };
 
+    source.attach(element);
+}
+
+fn compose(element: HtmlElement, animation_initializer: impl Initializer) {
+    reduced_motion::register(&element);
+    mount(
+        element,
+        animation_initializer,
+        &mut Handover::default(),
+        true,
+    );
+}
+
+/// Creates the animation, letting it take over the state of the one it
+/// replaces, and sets up its listeners within the current owner.
+fn mount(
+    element: HtmlElement,
+    animation_initializer: impl Initializer,
+    handover: &mut Handover,
+    entering: bool,
+) -> AnimationCell {
     let animation: AnimationCell = Rc::new(RefCell::new(
         animation_initializer.init_animation(element.clone()),
     ));
+    animation.borrow_mut().take_over(handover);
+
+    Composer {
+        element,
+        animation: Rc::clone(&animation),
+        entering,
+    }
+    .compose();
+
+    animation
+}
+
+/// Counterpart of the [`animate`] directive that returns an [`AnimationHandle`]
+/// for controlling the animations of the element once it is mounted.
+///
+/// ```no_run
+/// let node_ref = NodeRef::new();
+/// let handle = use_animate(node_ref, Flip::watch(items));
+///
+/// view! {
+///     <div node_ref=node_ref on:dragstart=move |_| handle.pause()>
+///         // ...
+///     </div>
+/// }
+/// ```
+pub fn use_animate<E>(
+    node_ref: NodeRef<E>,
+    source: impl AnimationSource + 'static,
+) -> AnimationHandle
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    let handle = AnimationHandle::new();
+
+    node_ref.on_load({
+        let handle = handle.clone();
+        move |element| {
+            let Ok(element) = element.dyn_into::<HtmlElement>() else {
+                log_error!("Could not animate a non-HtmlElement");
+                return;
+            };
+
+            handle.observe(&element);
+            source.attach(element);
+        }
+    });
 
-    Composer { element, animation }.compose();
+    handle
 }
@@ -0,0 +1,348 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    mem,
+    rc::Rc,
+};
+
+use leptos::prelude::queue_microtask;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{
+    self,
+    js_sys::{Array, Map},
+    MutationObserver,
+    MutationObserverInit,
+    MutationRecord,
+    Node,
+};
+
+type Callback = Rc<dyn Fn(&[MutationRecord])>;
+
+#[derive(Clone)]
+struct Subscriber {
+    id: usize,
+    node: Node,
+    cb: Callback,
+}
+
+/// Subscribers of a single node.
+struct Group {
+    node: Node,
+    subscribers: Vec<Subscriber>,
+}
+
+/// Subscribers of a root indexed by their nodes, so a mutation is dispatched by
+/// walking up from its target instead of checking every subscriber.
+#[derive(Default)]
+struct Subscribers {
+    /// Key of the group of every subscribed node.
+    keys: Map,
+    /// Groups in the order their nodes have been subscribed.
+    groups: BTreeMap<u32, Group>,
+    next_key: u32,
+}
+
+impl Subscribers {
+    fn key(&self, node: &Node) -> Option<u32> {
+        self.keys.get(node).as_f64().map(|key| key as u32)
+    }
+
+    fn push(&mut self, subscriber: Subscriber) {
+        if let Some(group) = self
+            .key(&subscriber.node)
+            .and_then(|key| self.groups.get_mut(&key))
+        {
+            group.subscribers.push(subscriber);
+            return;
+        }
+
+        let key = self.next_key;
+        self.next_key = self.next_key.wrapping_add(1);
+
+        self.keys.set(&subscriber.node, &key.into());
+        self.groups.insert(
+            key,
+            Group {
+                node: subscriber.node.clone(),
+                subscribers: vec![subscriber],
+            },
+        );
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.groups.retain(|_, group| {
+            group.subscribers.retain(|subscriber| subscriber.id != id);
+
+            if !group.subscribers.is_empty() {
+                return true;
+            }
+
+            self.keys.delete(&group.node);
+            false
+        });
+    }
+
+    fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    fn drain(&mut self) -> Vec<Subscriber> {
+        self.keys.clear();
+        mem::take(&mut self.groups)
+            .into_values()
+            .flat_map(|group| group.subscribers)
+            .collect()
+    }
+
+    /// Removes the subscribers whose nodes have been moved out of the root.
+    fn take_moved_out(&mut self, root: &Node) -> Vec<Subscriber> {
+        let mut moved_out = Vec::new();
+
+        self.groups.retain(|_, group| {
+            if root.contains(Some(&group.node)) {
+                return true;
+            }
+
+            self.keys.delete(&group.node);
+            moved_out.append(&mut group.subscribers);
+            false
+        });
+
+        moved_out
+    }
+
+    /// Records of every subscribed node's subtree, along with the callbacks of
+    /// the node.
+    fn batches(
+        &self,
+        root: &Node,
+        mutations: &[MutationRecord],
+    ) -> Vec<(Vec<Callback>, Vec<MutationRecord>)> {
+        let mut batches = BTreeMap::<u32, Vec<MutationRecord>>::new();
+        let root_key = self.key(root);
+
+        for mutation in mutations {
+            // the root is interested in everything, even in the nodes removed
+            // from its subtree in the meantime
+            if let Some(key) = root_key {
+                batches.entry(key).or_default().push(mutation.clone());
+            }
+
+            let mut node = mutation.target();
+
+            while let Some(current) = node {
+                if current == *root {
+                    break;
+                }
+
+                if let Some(key) = self.key(&current) {
+                    batches.entry(key).or_default().push(mutation.clone());
+                }
+
+                node = current.parent_node();
+            }
+        }
+
+        batches
+            .into_iter()
+            .filter_map(|(key, mutations)| {
+                let group = self.groups.get(&key)?;
+                let callbacks = group
+                    .subscribers
+                    .iter()
+                    .map(|subscriber| Rc::clone(&subscriber.cb))
+                    .collect();
+                Some((callbacks, mutations))
+            })
+            .collect()
+    }
+}
+
+/// A single `MutationObserver` attached to an outermost observed node, shared
+/// by every animation interested in mutations within its subtree, e.g. all the
+/// rows of a list and the list itself.
+struct Root {
+    node: Node,
+    observer: MutationObserver,
+    subscribers: Rc<RefCell<Subscribers>>,
+    _closure: Closure<dyn Fn(Array)>,
+}
+
+impl Root {
+    fn new(node: &Node) -> Self {
+        let subscribers = Rc::new(RefCell::new(Subscribers::default()));
+
+        let closure = Closure::<dyn Fn(Array)>::new({
+            let subscribers = Rc::clone(&subscribers);
+            let node = node.clone();
+            move |mutations: Array| {
+                let mutations = mutations
+                    .to_vec()
+                    .into_iter()
+                    .map(JsCast::unchecked_into)
+                    .collect::<Vec<MutationRecord>>();
+
+                // subscribers may (un)subscribe while being notified
+                let batches = subscribers.borrow().batches(&node, &mutations);
+
+                for (callbacks, mutations) in batches {
+                    for cb in callbacks {
+                        cb(&mutations);
+                    }
+                }
+
+                if !mutations
+                    .iter()
+                    .any(|mutation| mutation.type_() == "childList")
+                {
+                    return;
+                }
+
+                // nodes moved elsewhere are no longer observed by this root
+                let moved_out = subscribers.borrow_mut().take_moved_out(&node);
+                if !moved_out.is_empty() {
+                    REGISTRY.with(|registry| registry.reattach(moved_out));
+                }
+            }
+        });
+
+        let observer = MutationObserver::new(closure.as_ref().unchecked_ref()).unwrap();
+
+        let options = MutationObserverInit::new();
+        options.set_attributes(true);
+        options.set_child_list(true);
+        options.set_subtree(true);
+        observer.observe_with_options(node, &options).unwrap();
+
+        Self {
+            node: node.clone(),
+            observer,
+            subscribers,
+            _closure: closure,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    roots: RefCell<Vec<Root>>,
+    pending: RefCell<Vec<Subscriber>>,
+    next_subscriber_id: Cell<usize>,
+}
+
+impl Registry {
+    /// Adds the subscriber to the root containing its node. Without one, a new
+    /// root is created which takes over the roots nested within it.
+    fn attach(&self, subscriber: Subscriber) {
+        let mut roots = self.roots.borrow_mut();
+
+        if let Some(root) = roots
+            .iter()
+            .find(|root| root.node.contains(Some(&subscriber.node)))
+        {
+            root.subscribers.borrow_mut().push(subscriber);
+            return;
+        }
+
+        let root = Root::new(&subscriber.node);
+
+        roots.retain(|nested| {
+            if !root.node.contains(Some(&nested.node)) {
+                return true;
+            }
+
+            nested.observer.disconnect();
+
+            let mut subscribers = root.subscribers.borrow_mut();
+            for subscriber in nested.subscribers.borrow_mut().drain() {
+                subscribers.push(subscriber);
+            }
+
+            false
+        });
+
+        root.subscribers.borrow_mut().push(subscriber);
+        roots.push(root);
+    }
+
+    /// Attaches the subscribers at the end of the current task, once their
+    /// nodes are inserted in their new place. Their previous roots are
+    /// disconnected if nobody else is interested in them anymore.
+    fn reattach(&self, subscribers: Vec<Subscriber>) {
+        self.pending.borrow_mut().extend(subscribers);
+
+        queue_microtask(|| {
+            REGISTRY.with(|registry| {
+                let pending = mem::take(&mut *registry.pending.borrow_mut());
+
+                registry.prune();
+
+                for subscriber in pending {
+                    registry.attach(subscriber);
+                }
+            });
+        });
+    }
+
+    fn prune(&self) {
+        self.roots.borrow_mut().retain(|root| {
+            if !root.subscribers.borrow().is_empty() {
+                return true;
+            }
+
+            root.observer.disconnect();
+            false
+        });
+    }
+}
+
+thread_local! {
+    static REGISTRY: Registry = Registry::default();
+}
+
+pub struct Subscription {
+    id: usize,
+}
+
+/// Subscribes to mutations of the node's subtree, reusing the observer of an
+/// observed ancestor if there is one already. Nodes which are not in the
+/// document yet - such as elements being created - are attached once they get
+/// inserted, at the end of the current task. The same applies to nodes moved
+/// out of the subtree of their observer.
+pub fn subscribe(node: &Node, cb: impl Fn(&[MutationRecord]) + 'static) -> Subscription {
+    REGISTRY.with(|registry| {
+        let id = registry.next_subscriber_id.get();
+        registry.next_subscriber_id.set(id.wrapping_add(1));
+
+        let subscriber = Subscriber {
+            id,
+            node: node.clone(),
+            cb: Rc::new(cb),
+        };
+
+        if node.is_connected() {
+            registry.attach(subscriber);
+        } else {
+            registry.reattach(vec![subscriber]);
+        }
+
+        Subscription { id }
+    })
+}
+
+/// Removes the subscriber and disconnects the observer once nobody is
+/// interested in its subtree anymore.
+pub fn unsubscribe(subscription: &Subscription) {
+    REGISTRY.with(|registry| {
+        registry
+            .pending
+            .borrow_mut()
+            .retain(|subscriber| subscriber.id != subscription.id);
+
+        for root in registry.roots.borrow().iter() {
+            root.subscribers.borrow_mut().remove(subscription.id);
+        }
+
+        registry.prune();
+    });
+}
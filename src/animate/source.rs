@@ -0,0 +1,122 @@
+use std::{cell::RefCell, rc::Rc};
+
+use leptos::prelude::{untrack, Effect, Owner};
+use web_sys::HtmlElement;
+
+use super::{compose, initial::Initial, mount, AnimationCell, SWAPPING};
+use crate::{
+    animation::{Handover, Initializer},
+    reduced_motion,
+};
+
+/// Provides the animations attached by [`animate`](super::animate) and
+/// [`use_animate`](super::use_animate) - either an [`Initializer`] or
+/// a [`Reactive`] one.
+pub trait AnimationSource {
+    fn attach(self, element: HtmlElement);
+}
+
+impl<T> AnimationSource for T
+where
+    T: Initializer,
+{
+    fn attach(self, element: HtmlElement) {
+        compose(element, self);
+    }
+}
+
+/// Initializer recomputed whenever the signals it reads change, see
+/// [`reactive`].
+#[derive(Clone)]
+pub struct Reactive<F>(F);
+
+/// Swaps the animations of an element whenever the signals read by the
+/// closure change, without remounting the element:
+///
+/// ```no_run
+/// view! {
+///     <div use:animate=reactive(move || {
+///         if zoom.get() {
+///             Either::Left(zoom::In::default())
+///         } else {
+///             Either::Right(fade::In::default())
+///         }
+///     })>
+///         // ...
+///     </div>
+/// }
+/// ```
+///
+/// The replaced animations do not run their cleanup listeners, and hand their
+/// measured state over to the new ones where possible, e.g. a FLIP animation
+/// keeps tracking the last position of the element. The new animations skip
+/// the enter phase, as the element is already in place.
+pub const fn reactive<F, I>(initializer: F) -> Reactive<F>
+where
+    F: Fn() -> I + Clone + 'static,
+    I: Initializer,
+{
+    Reactive(initializer)
+}
+
+struct Mounted {
+    owner: Owner,
+    animation: AnimationCell,
+}
+
+fn mount_owned(
+    parent: Option<&Owner>,
+    element: &HtmlElement,
+    initializer: impl Initializer,
+    handover: &mut Handover,
+    entering: bool,
+) -> Mounted {
+    let owner = parent.map_or_else(Owner::new, Owner::child);
+    let animation =
+        owner.with(|| mount(element.clone(), initializer, handover, entering));
+
+    Mounted { owner, animation }
+}
+
+impl<F, I> AnimationSource for Reactive<F>
+where
+    F: Fn() -> I + 'static,
+    I: Initializer,
+{
+    fn attach(self, element: HtmlElement) {
+        let Self(initializer) = self;
+
+        reduced_motion::register(&element);
+
+        let parent = Owner::current();
+        let mounted = Rc::new(RefCell::new(mount_owned(
+            parent.as_ref(),
+            &element,
+            untrack(&initializer),
+            &mut Handover::default(),
+            true,
+        )));
+        let initial = Initial::new();
+
+        Effect::new(move || {
+            let next = initializer();
+
+            if initial.get() {
+                return;
+            }
+
+            untrack(|| {
+                let mut mounted = mounted.borrow_mut();
+                let mut handover = Handover::default();
+                mounted.animation.borrow().hand_over(&mut handover);
+
+                SWAPPING.set(true);
+                mounted.owner.cleanup();
+                SWAPPING.set(false);
+
+                *mounted =
+                    mount_owned(parent.as_ref(), &element, next, &mut handover, false);
+            });
+        });
+    }
+}
@@ -0,0 +1,48 @@
+use leptos::prelude::on_cleanup;
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{
+    self,
+    js_sys::Array,
+    HtmlElement,
+    IntersectionObserver,
+    IntersectionObserverEntry,
+    IntersectionObserverInit,
+};
+
+pub fn use_intersection_observer(
+    element: &HtmlElement,
+    thresholds: &[f64],
+    root_margin: &str,
+    cb: impl Fn(IntersectionObserverEntry) + 'static,
+) {
+    let closure = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+        for entry in entries {
+            cb(entry.unchecked_into());
+        }
+    });
+
+    let options = IntersectionObserverInit::new();
+    options.set_threshold_f64_sequence(
+        &thresholds
+            .iter()
+            .copied()
+            .map(JsValue::from)
+            .collect::<Array>(),
+    );
+    options.set_root_margin(root_margin);
+
+    let observer = IntersectionObserver::new_with_options(
+        closure.as_ref().unchecked_ref(),
+        &options,
+    )
+    .unwrap();
+    closure.forget();
+
+    observer.observe(element);
+
+    let observer = SendWrapper::new(observer);
+    on_cleanup(move || {
+        observer.disconnect();
+    });
+}
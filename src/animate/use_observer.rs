@@ -1,45 +1,22 @@
 use leptos::prelude::on_cleanup;
-use send_wrapper::SendWrapper;
-use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::{
-    self,
-    js_sys::Array,
-    HtmlElement,
-    MutationObserver,
-    MutationObserverInit,
-    MutationRecord,
-};
+use web_sys::{HtmlElement, MutationRecord};
 
-use super::initial::Initial;
+use super::{initial::Initial, observer_registry};
 
-pub fn use_observer(element: &HtmlElement, cb: impl Fn(Vec<MutationRecord>) + 'static) {
-    let initial = Initial::new();
+pub fn use_observer(element: &HtmlElement, cb: impl Fn(&[MutationRecord]) + 'static) {
+    // a detached element is attached to the registry only after being
+    // inserted, so the mutations of its creation are not observed at all
+    let initial = element.is_connected().then(Initial::new);
 
-    let closure = Closure::<dyn Fn(Array)>::new(move |mutations: Array| {
-        if initial.get() {
+    let subscription = observer_registry::subscribe(element, move |mutations| {
+        if initial.as_ref().is_some_and(Initial::get) {
             return;
         }
 
-        let mutations = mutations
-            .to_vec()
-            .into_iter()
-            .map(JsCast::unchecked_into)
-            .collect::<Vec<_>>();
-
         cb(mutations);
     });
 
-    let observer = MutationObserver::new(closure.as_ref().unchecked_ref()).unwrap();
-    closure.forget();
-
-    let options = MutationObserverInit::new();
-    options.set_attributes(true);
-    options.set_child_list(true);
-    options.set_subtree(true);
-    observer.observe_with_options(element, &options).unwrap();
-
-    let observer = SendWrapper::new(observer);
     on_cleanup(move || {
-        observer.disconnect();
+        observer_registry::unsubscribe(&subscription);
     });
 }
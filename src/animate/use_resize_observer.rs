@@ -0,0 +1,25 @@
+use leptos::prelude::on_cleanup;
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{self, js_sys::Array, HtmlElement, ResizeObserver, ResizeObserverEntry};
+
+pub fn use_resize_observer(
+    element: &HtmlElement,
+    cb: impl Fn(ResizeObserverEntry) + 'static,
+) {
+    let closure = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+        for entry in entries {
+            cb(entry.unchecked_into());
+        }
+    });
+
+    let observer = ResizeObserver::new(closure.as_ref().unchecked_ref()).unwrap();
+    closure.forget();
+
+    observer.observe(element);
+
+    let observer = SendWrapper::new(observer);
+    on_cleanup(move || {
+        observer.disconnect();
+    });
+}
@@ -1,5 +1,3 @@
-use std::{cell::RefCell, rc::Rc};
-
 use web_sys::HtmlElement;
 
 pub trait Initializer: Clone {
@@ -8,6 +6,8 @@ pub trait Initializer: Clone {
 
 pub mod listeners;
 
+pub use leptos_animate_macros::Animation;
+
 pub trait Animation:
     listeners::BeforeEnter
     + listeners::Enter
@@ -15,6 +15,8 @@ pub trait Animation:
     + listeners::Effect
     + listeners::Mutation
     + listeners::ParentMutation
+    + listeners::Visibility
+    + listeners::ElementResize
     + listeners::EnterAnimationsFinished
     + listeners::MutationAnimationsFinished
     + listeners::ParentMutationAnimationsFinished
@@ -26,44 +28,30 @@ pub trait Animation:
     }
 
     fn track(&self) {}
+
+    /// Puts the state worth keeping for the animation replacing this one.
+    fn hand_over(&self, _handover: &mut Handover) {}
+
+    /// Takes the state left by the replaced animation.
+    fn take_over(&mut self, _handover: &mut Handover) {}
 }
 
 mod aggregated;
 use aggregated::Aggregated;
 
+mod composed;
+
+mod dynamic;
+pub use dynamic::DynInitializer;
+
 mod ext;
 pub use ext::Ext;
 
-macro_rules! impl_animation_initializer {
-    ($($idx:tt $t:tt),+) => {
-        impl<$($t,)+> Initializer for ($($t,)+)
-        where
-            $($t: Initializer),+
-        {
-            fn init_animation(self, element: HtmlElement) -> impl Animation {
-                Aggregated::new(
-                    vec![
-                        $(
-                            Rc::new(
-                                RefCell::new(self.$idx.init_animation(element.clone()))
-                            )
-                        ),+
-                    ],
-                    element
-                )
-            }
-        }
-    };
-}
+mod handover;
+pub use handover::Handover;
 
-impl_animation_initializer!(0 A);
-impl_animation_initializer!(0 A, 1 B);
-impl_animation_initializer!(0 A, 1 B, 2 C);
-impl_animation_initializer!(0 A, 1 B, 2 C, 3 D);
-impl_animation_initializer!(0 A, 1 B, 2 C, 3 D, 4 E);
-impl_animation_initializer!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
-impl_animation_initializer!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
-impl_animation_initializer!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+mod initializer_ext;
+pub use initializer_ext::{Adjusted, InitializerExt};
 
 #[macro_export]
 macro_rules! impl_empty_animation_listeners {
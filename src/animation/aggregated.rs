@@ -4,9 +4,14 @@ use std::{
     rc::Rc,
 };
 
-use web_sys::{HtmlElement, MutationRecord};
+use web_sys::{
+    HtmlElement,
+    IntersectionObserverEntry,
+    MutationRecord,
+    ResizeObserverEntry,
+};
 
-use super::{listeners, Animation, Ext};
+use super::{listeners, Animation, Ext, Handover};
 
 fn collect_indexes(
     animations: &[Rc<RefCell<dyn Animation>>],
@@ -63,6 +68,8 @@ aggregated_struct! {
     effect,
     mutation,
     parent_mutation,
+    visibility,
+    element_resize,
     enter_animations_finished,
     mutation_animations_finished,
     parent_mutation_animations_finished,
@@ -112,6 +119,14 @@ impl Aggregated {
             f(m);
         }
     }
+
+    fn first_listening_for_visibility(&self) -> Option<&Rc<RefCell<dyn Animation>>> {
+        self.animations
+            .iter()
+            .enumerate()
+            .find(|(i, _)| self.idx_listening_for_visibility.contains(i))
+            .map(|(_, m)| m)
+    }
 }
 
 impl listeners::Enter for Aggregated {
@@ -162,6 +177,52 @@ impl listeners::ParentMutation for Aggregated {
     }
 }
 
+/// All aggregated animations share a single observer. It is notified at the
+/// thresholds of all of them, while the root margin is taken from the first
+/// animation listening for visibility changes.
+impl listeners::Visibility for Aggregated {
+    fn listening_for_visibility(&self) -> bool {
+        !self.idx_listening_for_visibility.is_empty()
+    }
+
+    fn visibility_threshold(&self) -> f64 {
+        self.first_listening_for_visibility()
+            .map_or(0.0, |m| m.borrow().visibility_threshold())
+    }
+
+    fn visibility_thresholds(&self) -> Vec<f64> {
+        self.animations
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.idx_listening_for_visibility.contains(i))
+            .flat_map(|(_, m)| m.borrow().visibility_thresholds())
+            .collect()
+    }
+
+    fn visibility_root_margin(&self) -> String {
+        self.first_listening_for_visibility()
+            .map_or_else(|| "0px".to_owned(), |m| m.borrow().visibility_root_margin())
+    }
+
+    fn on_visibility(&mut self, entry: &IntersectionObserverEntry) {
+        self.each_listening(&self.idx_listening_for_visibility, |m| {
+            m.borrow_mut().on_visibility(entry);
+        });
+    }
+}
+
+impl listeners::ElementResize for Aggregated {
+    fn listening_for_element_resize(&self) -> bool {
+        !self.idx_listening_for_element_resize.is_empty()
+    }
+
+    fn on_element_resize(&mut self, entry: &ResizeObserverEntry) {
+        self.each_listening(&self.idx_listening_for_element_resize, |m| {
+            m.borrow_mut().on_element_resize(entry);
+        });
+    }
+}
+
 impl Animation for Aggregated {
     fn enabled(&self) -> bool {
         self.animations.iter().any(|m| m.borrow().enabled())
@@ -174,4 +235,16 @@ impl Animation for Aggregated {
             }
         }
     }
+
+    fn hand_over(&self, handover: &mut Handover) {
+        for m in &self.animations {
+            m.borrow().hand_over(handover);
+        }
+    }
+
+    fn take_over(&mut self, handover: &mut Handover) {
+        for m in &self.animations {
+            m.borrow_mut().take_over(handover);
+        }
+    }
 }
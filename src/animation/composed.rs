@@ -0,0 +1,230 @@
+use std::time::Duration;
+
+use web_sys::{
+    HtmlElement,
+    IntersectionObserverEntry,
+    MutationRecord,
+    ResizeObserverEntry,
+};
+
+use super::{listeners, Animation, Handover, Initializer};
+
+/// Statically composed animations of a tuple initializer.
+///
+/// Unlike [`Aggregated`](super::Aggregated), the animations keep their
+/// concrete types, so the events are dispatched without dynamic calls or
+/// index lookups, and constant listener flags get inlined. The animations are
+/// held by value - the delayed events are dispatched to each of them as a
+/// separate part, see [`Enter::enter_delays`](listeners::Enter::enter_delays).
+pub struct Composed<T> {
+    animations: T,
+}
+
+fn runs<A: Animation>(animation: &A, listening: impl Fn(&A) -> bool) -> bool {
+    listening(animation) && animation.enabled()
+}
+
+macro_rules! impl_composed_listener {
+    (($($idx:tt $t:ident),+); $listener:ident ($($param:ident: $param_ty:ty),*) $args:tt) => {
+        paste::paste! {
+            impl<$($t,)+> listeners::$listener for Composed<($($t,)+)>
+            where
+                $($t: Animation),+
+            {
+                fn [< listening_for_ $listener:snake >](&self) -> bool {
+                    $(
+                        self.animations.$idx.[< listening_for_ $listener:snake >]()
+                    )||+
+                }
+
+                fn [< on_ $listener:snake >](&mut self, $($param: $param_ty),*) {
+                    $(
+                        if runs(&self.animations.$idx, |a| a.[< listening_for_ $listener:snake >]()) {
+                            self.animations.$idx.[< on_ $listener:snake >] $args;
+                        }
+                    )+
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_composed_delayed_listener {
+    (($($idx:tt $t:ident),+); $listener:ident) => {
+        paste::paste! {
+            impl<$($t,)+> listeners::$listener for Composed<($($t,)+)>
+            where
+                $($t: Animation),+
+            {
+                fn [< listening_for_ $listener:snake >](&self) -> bool {
+                    $(
+                        self.animations.$idx.[< listening_for_ $listener:snake >]()
+                    )||+
+                }
+
+                /// Parts of every listening animation, in order.
+                fn [< $listener:snake _delays >](&self) -> Vec<Duration> {
+                    let mut delays = Vec::new();
+                    $(
+                        if self.animations.$idx.[< listening_for_ $listener:snake >]() {
+                            delays.extend(self.animations.$idx.[< $listener:snake _delays >]());
+                        }
+                    )+
+                    delays
+                }
+
+                fn [< on_ $listener:snake >](&mut self) {
+                    $(
+                        if runs(&self.animations.$idx, |a| a.[< listening_for_ $listener:snake >]()) {
+                            self.animations.$idx.[< on_ $listener:snake >]();
+                        }
+                    )+
+                }
+
+                // the offset is left unread after the last animation
+                #[allow(unused_assignments)]
+                fn [< on_ $listener:snake _part >](&mut self, part: usize) {
+                    let mut first = 0;
+                    $(
+                        if self.animations.$idx.[< listening_for_ $listener:snake >]() {
+                            let parts = self.animations.$idx.[< $listener:snake _delays >]().len();
+
+                            if part < first + parts {
+                                if self.animations.$idx.enabled() {
+                                    self.animations.$idx.[< on_ $listener:snake _part >](part - first);
+                                }
+                                return;
+                            }
+
+                            first += parts;
+                        }
+                    )+
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_composed {
+    ($($idx:tt $t:ident),+) => {
+        impl<$($t,)+> Initializer for ($($t,)+)
+        where
+            $($t: Initializer),+
+        {
+            fn init_animation(self, element: HtmlElement) -> impl Animation {
+                Composed {
+                    animations: ($(self.$idx.init_animation(element.clone()),)+),
+                }
+            }
+        }
+
+        impl_composed_listener!(($($idx $t),+); BeforeEnter() ());
+        impl_composed_delayed_listener!(($($idx $t),+); Enter);
+        impl_composed_listener!(($($idx $t),+); ImmediateEffect() ());
+        impl_composed_delayed_listener!(($($idx $t),+); Effect);
+        impl_composed_listener!(($($idx $t),+); Mutation(mutations: &[MutationRecord]) (mutations));
+        impl_composed_listener!(($($idx $t),+); ParentMutation(mutations: &[MutationRecord]) (mutations));
+        impl_composed_listener!(($($idx $t),+); ElementResize(entry: &ResizeObserverEntry) (entry));
+        impl_composed_listener!(($($idx $t),+); EnterAnimationsFinished() ());
+        impl_composed_listener!(($($idx $t),+); MutationAnimationsFinished() ());
+        impl_composed_listener!(($($idx $t),+); ParentMutationAnimationsFinished() ());
+        impl_composed_listener!(($($idx $t),+); Cleanup() ());
+
+        /// All composed animations share a single observer. It is notified at
+        /// the thresholds of all of them, while the root margin is taken from
+        /// the first animation listening for visibility changes.
+        impl<$($t,)+> listeners::Visibility for Composed<($($t,)+)>
+        where
+            $($t: Animation),+
+        {
+            fn listening_for_visibility(&self) -> bool {
+                $(self.animations.$idx.listening_for_visibility())||+
+            }
+
+            fn visibility_threshold(&self) -> f64 {
+                $(
+                    let animation = &self.animations.$idx;
+                    if animation.listening_for_visibility() {
+                        return animation.visibility_threshold();
+                    }
+                )+
+                0.0
+            }
+
+            fn visibility_thresholds(&self) -> Vec<f64> {
+                let mut thresholds = Vec::new();
+                $(
+                    let animation = &self.animations.$idx;
+                    if animation.listening_for_visibility() {
+                        thresholds.extend(animation.visibility_thresholds());
+                    }
+                )+
+                thresholds
+            }
+
+            fn visibility_root_margin(&self) -> String {
+                $(
+                    let animation = &self.animations.$idx;
+                    if animation.listening_for_visibility() {
+                        return animation.visibility_root_margin();
+                    }
+                )+
+                "0px".to_owned()
+            }
+
+            fn on_visibility(&mut self, entry: &IntersectionObserverEntry) {
+                $(
+                    if runs(&self.animations.$idx, |a| a.listening_for_visibility()) {
+                        self.animations.$idx.on_visibility(entry);
+                    }
+                )+
+            }
+        }
+
+        impl<$($t,)+> Animation for Composed<($($t,)+)>
+        where
+            $($t: Animation),+
+        {
+            fn enabled(&self) -> bool {
+                $(self.animations.$idx.enabled())||+
+            }
+
+            fn track(&self) {
+                $(
+                    let animation = &self.animations.$idx;
+                    if animation.listening_for_immediate_effect()
+                        || animation.listening_for_effect()
+                    {
+                        animation.track();
+                    }
+                )+
+            }
+
+            fn hand_over(&self, handover: &mut Handover) {
+                $(self.animations.$idx.hand_over(handover);)+
+            }
+
+            fn take_over(&mut self, handover: &mut Handover) {
+                $(self.animations.$idx.take_over(handover);)+
+            }
+        }
+    };
+}
+
+/// A single animation needs no composition at all.
+impl<A> Initializer for (A,)
+where
+    A: Initializer,
+{
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        self.0.init_animation(element)
+    }
+}
+
+impl_composed!(0 A, 1 B);
+impl_composed!(0 A, 1 B, 2 C);
+impl_composed!(0 A, 1 B, 2 C, 3 D);
+impl_composed!(0 A, 1 B, 2 C, 3 D, 4 E);
+impl_composed!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+impl_composed!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+impl_composed!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
@@ -0,0 +1,107 @@
+use std::{cell::RefCell, rc::Rc};
+
+use leptos::either::Either;
+use web_sys::HtmlElement;
+
+use super::{Aggregated, Animation, Initializer};
+
+/// Object-safe counterpart of [`Initializer`], implemented for every
+/// initializer. It allows to pick the animations at runtime, e.g. based on
+/// user preferences, without naming their concrete types:
+///
+/// ```no_run
+/// fn animations(
+///     items: RwSignal<Vec<usize>>,
+///     with_fade: bool,
+/// ) -> Vec<Box<dyn DynInitializer>> {
+///     let mut animations: Vec<Box<dyn DynInitializer>> =
+///         vec![Box::new(Flip::watch(items))];
+///     if with_fade {
+///         animations.push(Box::new((fade::In::default(), fade::Out::default())));
+///     }
+///     animations
+/// }
+/// ```
+pub trait DynInitializer {
+    fn init_dyn_animation(&self, element: HtmlElement) -> Rc<RefCell<dyn Animation>>;
+
+    fn clone_box(&self) -> Box<dyn DynInitializer>;
+}
+
+fn init_cell(
+    initializer: impl Initializer,
+    element: HtmlElement,
+) -> Rc<RefCell<dyn Animation>> {
+    Rc::new(RefCell::new(initializer.init_animation(element)))
+}
+
+impl<T> DynInitializer for T
+where
+    T: Initializer + 'static,
+{
+    fn init_dyn_animation(&self, element: HtmlElement) -> Rc<RefCell<dyn Animation>> {
+        init_cell(self.clone(), element)
+    }
+
+    fn clone_box(&self) -> Box<dyn DynInitializer> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynInitializer> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl Initializer for Box<dyn DynInitializer> {
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        Aggregated::new(vec![self.init_dyn_animation(element.clone())], element)
+    }
+}
+
+/// Runs all of the animations, just like a tuple.
+impl<T> Initializer for Vec<T>
+where
+    T: Initializer,
+{
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        let animations = self
+            .into_iter()
+            .map(|initializer| init_cell(initializer, element.clone()))
+            .collect();
+
+        Aggregated::new(animations, element)
+    }
+}
+
+/// Does not animate the element when `None`.
+impl<T> Initializer for Option<T>
+where
+    T: Initializer,
+{
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        let animations = self
+            .into_iter()
+            .map(|initializer| init_cell(initializer, element.clone()))
+            .collect();
+
+        Aggregated::new(animations, element)
+    }
+}
+
+/// Runs one of two animations of different types.
+impl<L, R> Initializer for Either<L, R>
+where
+    L: Initializer,
+    R: Initializer,
+{
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        let animation = match self {
+            Self::Left(initializer) => init_cell(initializer, element.clone()),
+            Self::Right(initializer) => init_cell(initializer, element.clone()),
+        };
+
+        Aggregated::new(vec![animation], element)
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::{RefCell, RefMut},
     rc::Rc,
     time::Duration,
 };
@@ -8,28 +8,32 @@ use leptos::task::spawn_local;
 use web_sys::HtmlElement;
 
 use super::Animation;
-use crate::utils::sleep;
+use crate::{animation_mode, reduced_motion, time_scale, utils::sleep};
 
 pub trait Ext {
     fn delayed_on_enter(&self, element: &HtmlElement);
     fn delayed_on_effect(&self, element: &HtmlElement);
 }
 
-type AnimationCell = Rc<RefCell<dyn Animation>>;
-
-fn should_run(animation: &AnimationCell, element: &HtmlElement) -> bool {
-    animation.borrow().enabled() && element.is_connected()
+fn should_run<A>(animation: &Rc<RefCell<A>>, element: &HtmlElement) -> bool
+where
+    A: Animation + ?Sized,
+{
+    animation.borrow().enabled()
+        && !animation_mode::is_off()
+        && !reduced_motion::is_disabled(element)
+        && element.is_connected()
 }
 
-fn run_now_or_after_delay(
-    animation: &AnimationCell,
+fn run_now_or_after_delay<A>(
+    animation: &Rc<RefCell<A>>,
     element: &HtmlElement,
-    delay: impl Fn(Ref<'_, dyn Animation>) -> Duration,
-    cb: impl Fn(RefMut<'_, dyn Animation>) + 'static,
-) {
-    let delay = delay(animation.borrow());
-
-    if delay.is_zero() {
+    delay: Duration,
+    cb: impl Fn(RefMut<'_, A>) + 'static,
+) where
+    A: Animation + ?Sized,
+{
+    if delay.is_zero() || animation_mode::is_immediate() {
         if should_run(animation, element) {
             cb(animation.borrow_mut());
         }
@@ -39,7 +43,7 @@ fn run_now_or_after_delay(
     let animation = Rc::clone(animation);
     let element = element.clone();
     spawn_local(async move {
-        sleep(delay).await;
+        sleep(time_scale::scaled(delay)).await;
 
         if should_run(&animation, &element) {
             cb(animation.borrow_mut());
@@ -47,17 +51,28 @@ fn run_now_or_after_delay(
     });
 }
 
-impl Ext for AnimationCell {
+impl<A> Ext for Rc<RefCell<A>>
+where
+    A: Animation + ?Sized,
+{
+    /// Runs each part of the animation once its own delay is over.
     fn delayed_on_enter(&self, element: &HtmlElement) {
-        run_now_or_after_delay(self, element, |a| a.enter_delay(), |mut a| a.on_enter());
+        let delays = self.borrow().enter_delays();
+
+        for (part, delay) in delays.into_iter().enumerate() {
+            run_now_or_after_delay(self, element, delay, move |mut a| {
+                a.on_enter_part(part);
+            });
+        }
     }
 
     fn delayed_on_effect(&self, element: &HtmlElement) {
-        run_now_or_after_delay(
-            self,
-            element,
-            |a| a.effect_delay(),
-            |mut a| a.on_effect(),
-        );
+        let delays = self.borrow().effect_delays();
+
+        for (part, delay) in delays.into_iter().enumerate() {
+            run_now_or_after_delay(self, element, delay, move |mut a| {
+                a.on_effect_part(part);
+            });
+        }
     }
 }
@@ -0,0 +1,23 @@
+use std::any::Any;
+
+/// State passed from an animation to its replacement when the animations of
+/// an element are swapped via [`reactive`](crate::reactive), so the new one
+/// does not need to start from scratch, e.g. a FLIP animation keeps the last
+/// measured position of the element.
+///
+/// Each piece of state is identified by its type, so animations usually put a
+/// private type holding their state, and take it back when they find it.
+#[derive(Default)]
+pub struct Handover(Vec<Box<dyn Any>>);
+
+impl Handover {
+    pub fn put<T: 'static>(&mut self, state: T) {
+        self.0.push(Box::new(state));
+    }
+
+    /// Takes the first piece of state of the given type, if any.
+    pub fn take<T: 'static>(&mut self) -> Option<T> {
+        let idx = self.0.iter().position(|state| state.is::<T>())?;
+        self.0.remove(idx).downcast().ok().map(|state| *state)
+    }
+}
@@ -0,0 +1,443 @@
+use std::{rc::Rc, time::Duration};
+
+use leptos::prelude::{GetUntracked, Signal};
+use web_sys::{
+    HtmlElement,
+    IntersectionObserverEntry,
+    MutationRecord,
+    ResizeObserverEntry,
+};
+
+use super::{listeners, Animation, Handover, Initializer};
+use crate::{
+    timing_scope::{self, MapTiming, Timing},
+    AnimationTiming,
+    Stagger,
+};
+
+/// Combinators wrapping any initializer, e.g. to build app-specific variants
+/// of the built-in or third-party animations:
+///
+/// ```no_run
+/// view! {
+///     <div use:animate=(
+///         Flip::watch(items).when(reorderable),
+///         (fade::In::default(), slide::In::default()).delay(Duration::from_millis(100)),
+///         classes::Out::default().leave_only(),
+///     )>
+///         // ...
+///     </div>
+/// }
+/// ```
+pub trait InitializerExt: Initializer + Sized {
+    /// Runs the animation only while the condition holds.
+    fn when(self, condition: impl Into<Signal<bool>>) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::When(condition.into()))
+    }
+
+    /// Delays the animation on top of its own delay. The built-in
+    /// initializers have a `delay` setter of their own, which takes precedence
+    /// when called directly on them.
+    fn delay(self, delay: Duration) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::Delay(delay))
+    }
+
+    /// Maps the timing of every animation the wrapped one spawns, e.g. to cap
+    /// the duration. The mapping receives the timing the animation has asked
+    /// for, before the other adjustments are applied.
+    fn map_timing(
+        self,
+        map: impl Fn(AnimationTiming) -> AnimationTiming + 'static,
+    ) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::MapTiming(Rc::new(map)))
+    }
+
+    /// Delays the animation by the position of the element among its
+    /// siblings, see [`Stagger`].
+    fn stagger(self, stagger: Stagger) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::Stagger(stagger))
+    }
+
+    /// Plays the animation faster (above `1.0`) or slower (below `1.0`),
+    /// including its delays.
+    fn time_scale(self, scale: f64) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::TimeScale(scale.max(MIN_SCALE)))
+    }
+
+    /// Keeps only the enter phase of the animation, i.e. its `BeforeEnter`,
+    /// `Enter` and `EnterAnimationsFinished` listeners.
+    fn enter_only(self) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::Only(Phase::Enter))
+    }
+
+    /// Keeps only the leave phase of the animation, i.e. its `Cleanup`
+    /// listener along with the ones tracking the element until it leaves -
+    /// `Mutation`, `ParentMutation` and the `*AnimationsFinished` ones, e.g.
+    /// the [`Zombie`](crate::animations::zombie::Zombie) of a leave animation
+    /// records the position of the element in them.
+    fn leave_only(self) -> Adjusted<Self> {
+        Adjusted::new(self, Adjustment::Only(Phase::Leave))
+    }
+}
+
+impl<T> InitializerExt for T where T: Initializer {}
+
+const MIN_SCALE: f64 = 0.01;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Enter,
+    Leave,
+    Other,
+}
+
+#[derive(Clone)]
+enum Adjustment {
+    When(Signal<bool>),
+    Delay(Duration),
+    Stagger(Stagger),
+    TimeScale(f64),
+    MapTiming(MapTiming),
+    Only(Phase),
+}
+
+/// Initializer adjusted via [`InitializerExt`].
+#[must_use]
+#[derive(Clone)]
+pub struct Adjusted<I> {
+    initializer: I,
+    adjustment: Adjustment,
+}
+
+impl<I> Adjusted<I> {
+    const fn new(initializer: I, adjustment: Adjustment) -> Self {
+        Self {
+            initializer,
+            adjustment,
+        }
+    }
+}
+
+impl<I> Initializer for Adjusted<I>
+where
+    I: Initializer,
+{
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        AdjustedAnimation {
+            animation: self.initializer.init_animation(element.clone()),
+            adjustment: self.adjustment,
+            element,
+        }
+    }
+}
+
+struct AdjustedAnimation<A> {
+    animation: A,
+    adjustment: Adjustment,
+    element: HtmlElement,
+}
+
+impl<A> AdjustedAnimation<A> {
+    /// Whether a listener serving the phases is kept.
+    fn allows(&self, phases: &[Phase]) -> bool {
+        match self.adjustment {
+            Adjustment::Only(only) => phases.contains(&only),
+            _ => true,
+        }
+    }
+
+    /// Timing the listeners run within. The additional delay is skipped for
+    /// the listeners postponed by it already.
+    fn timing(&self, postponed: bool) -> Timing {
+        let current = timing_scope::current();
+
+        match &self.adjustment {
+            Adjustment::Delay(delay) if !postponed => current.then(*delay, 1.0),
+            // the position of an element being created is known only once it
+            // is inserted
+            Adjustment::Stagger(stagger)
+                if !postponed && self.element.parent_element().is_none() =>
+            {
+                let element = self.element.clone();
+                let stagger = *stagger;
+                current.then_deferred(move || stagger.delay(&element))
+            }
+            Adjustment::Stagger(stagger) if !postponed => {
+                current.then(stagger.delay(&self.element), 1.0)
+            }
+            Adjustment::TimeScale(scale) => current.then(Duration::ZERO, *scale),
+            Adjustment::MapTiming(map) => current.then_mapped(Rc::clone(map)),
+            _ => current,
+        }
+    }
+
+    /// Adjusts the delay of a listener. Only the `Enter` one is postponed by
+    /// the additional delay - the others would let the element jump to its new
+    /// layout in the meantime, so the animations they spawn are delayed
+    /// instead.
+    fn adjust_delay(&self, delay: Duration, postponed: bool) -> Duration {
+        match &self.adjustment {
+            Adjustment::Delay(additional) if postponed => delay + *additional,
+            Adjustment::Stagger(stagger) if postponed => {
+                delay + stagger.delay(&self.element)
+            }
+            Adjustment::TimeScale(scale) => delay.div_f64(*scale),
+            _ => delay,
+        }
+    }
+}
+
+macro_rules! impl_adjusted_listener {
+    ($listener:ident, [$($phase:ident),+] ($($param:ident: $param_ty:ty),*)) => {
+        paste::paste! {
+            impl<A> listeners::$listener for AdjustedAnimation<A>
+            where
+                A: Animation,
+            {
+                fn [< listening_for_ $listener:snake >](&self) -> bool {
+                    self.allows(&[$(Phase::$phase),+])
+                        && self.animation.[< listening_for_ $listener:snake >]()
+                }
+
+                fn [< on_ $listener:snake >](&mut self, $($param: $param_ty),*) {
+                    timing_scope::within(self.timing(false), || {
+                        self.animation.[< on_ $listener:snake >]($($param),*);
+                    });
+                }
+            }
+        }
+    };
+}
+
+impl_adjusted_listener!(BeforeEnter, [Enter]());
+impl_adjusted_listener!(ImmediateEffect, [Other]());
+impl_adjusted_listener!(Mutation, [Other, Leave](mutations: &[MutationRecord]));
+impl_adjusted_listener!(ParentMutation, [Other, Leave](mutations: &[MutationRecord]));
+impl_adjusted_listener!(ElementResize, [Other](entry: &ResizeObserverEntry));
+impl_adjusted_listener!(EnterAnimationsFinished, [Enter, Leave]());
+impl_adjusted_listener!(MutationAnimationsFinished, [Other, Leave]());
+impl_adjusted_listener!(ParentMutationAnimationsFinished, [Other, Leave]());
+impl_adjusted_listener!(Cleanup, [Leave]());
+
+impl<A> listeners::Enter for AdjustedAnimation<A>
+where
+    A: Animation,
+{
+    fn listening_for_enter(&self) -> bool {
+        self.allows(&[Phase::Enter]) && self.animation.listening_for_enter()
+    }
+
+    fn enter_delay(&self) -> Duration {
+        self.adjust_delay(self.animation.enter_delay(), true)
+    }
+
+    fn enter_delays(&self) -> Vec<Duration> {
+        self.animation
+            .enter_delays()
+            .into_iter()
+            .map(|delay| self.adjust_delay(delay, true))
+            .collect()
+    }
+
+    fn on_enter(&mut self) {
+        timing_scope::within(self.timing(true), || self.animation.on_enter());
+    }
+
+    fn on_enter_part(&mut self, part: usize) {
+        timing_scope::within(self.timing(true), || {
+            self.animation.on_enter_part(part);
+        });
+    }
+}
+
+impl<A> listeners::Effect for AdjustedAnimation<A>
+where
+    A: Animation,
+{
+    fn listening_for_effect(&self) -> bool {
+        self.allows(&[Phase::Other]) && self.animation.listening_for_effect()
+    }
+
+    fn effect_delay(&self) -> Duration {
+        self.adjust_delay(self.animation.effect_delay(), false)
+    }
+
+    fn effect_delays(&self) -> Vec<Duration> {
+        self.animation
+            .effect_delays()
+            .into_iter()
+            .map(|delay| self.adjust_delay(delay, false))
+            .collect()
+    }
+
+    fn on_effect(&mut self) {
+        timing_scope::within(self.timing(false), || self.animation.on_effect());
+    }
+
+    fn on_effect_part(&mut self, part: usize) {
+        timing_scope::within(self.timing(false), || {
+            self.animation.on_effect_part(part);
+        });
+    }
+}
+
+impl<A> listeners::Visibility for AdjustedAnimation<A>
+where
+    A: Animation,
+{
+    fn listening_for_visibility(&self) -> bool {
+        self.allows(&[Phase::Other]) && self.animation.listening_for_visibility()
+    }
+
+    fn visibility_threshold(&self) -> f64 {
+        self.animation.visibility_threshold()
+    }
+
+    fn visibility_thresholds(&self) -> Vec<f64> {
+        self.animation.visibility_thresholds()
+    }
+
+    fn visibility_root_margin(&self) -> String {
+        self.animation.visibility_root_margin()
+    }
+
+    fn on_visibility(&mut self, entry: &IntersectionObserverEntry) {
+        timing_scope::within(self.timing(false), || {
+            self.animation.on_visibility(entry);
+        });
+    }
+}
+
+impl<A> Animation for AdjustedAnimation<A>
+where
+    A: Animation,
+{
+    fn enabled(&self) -> bool {
+        let allowed = match &self.adjustment {
+            Adjustment::When(condition) => condition.get_untracked(),
+            _ => true,
+        };
+
+        allowed && self.animation.enabled()
+    }
+
+    fn track(&self) {
+        self.animation.track();
+    }
+
+    fn hand_over(&self, handover: &mut Handover) {
+        self.animation.hand_over(handover);
+    }
+
+    fn take_over(&mut self, handover: &mut Handover) {
+        self.animation.take_over(handover);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    use super::*;
+    use crate::animation::listeners::{
+        BeforeEnter,
+        Cleanup,
+        Effect,
+        ElementResize,
+        Enter,
+        EnterAnimationsFinished,
+        ImmediateEffect,
+        Mutation,
+        MutationAnimationsFinished,
+        ParentMutation,
+        ParentMutationAnimationsFinished,
+        Visibility,
+    };
+
+    /// Listens for everything, like a combination of enter, leave and layout
+    /// animations.
+    struct Everything;
+
+    macro_rules! impl_listening {
+        ($($listener:ident),+) => {
+            paste::paste! {
+                $(
+                    impl listeners::$listener for Everything {
+                        fn [< listening_for_ $listener:snake >](&self) -> bool {
+                            true
+                        }
+                    }
+                )+
+            }
+        };
+    }
+
+    impl_listening!(
+        BeforeEnter,
+        Enter,
+        ImmediateEffect,
+        Effect,
+        Mutation,
+        ParentMutation,
+        Visibility,
+        ElementResize,
+        EnterAnimationsFinished,
+        MutationAnimationsFinished,
+        ParentMutationAnimationsFinished,
+        Cleanup
+    );
+
+    impl Animation for Everything {}
+
+    fn only(phase: Phase) -> AdjustedAnimation<Everything> {
+        AdjustedAnimation {
+            animation: Everything,
+            adjustment: Adjustment::Only(phase),
+            // never accessed by the listening flags
+            element: JsValue::NULL.unchecked_into(),
+        }
+    }
+
+    fn listening(animation: &AdjustedAnimation<Everything>) -> [bool; 12] {
+        [
+            animation.listening_for_before_enter(),
+            animation.listening_for_enter(),
+            animation.listening_for_immediate_effect(),
+            animation.listening_for_effect(),
+            animation.listening_for_mutation(),
+            animation.listening_for_parent_mutation(),
+            animation.listening_for_visibility(),
+            animation.listening_for_element_resize(),
+            animation.listening_for_enter_animations_finished(),
+            animation.listening_for_mutation_animations_finished(),
+            animation.listening_for_parent_mutation_animations_finished(),
+            animation.listening_for_cleanup(),
+        ]
+    }
+
+    #[test]
+    fn enter_only_keeps_the_enter_listeners() {
+        assert_eq!(
+            listening(&only(Phase::Enter)),
+            [
+                true, true, false, false, false, false, false, false, true, false, false,
+                false
+            ]
+        );
+    }
+
+    /// The `Zombie` of a leave animation, e.g. `fade::Out::default()
+    /// .leave_only()`, needs to know where the element was before it left,
+    /// while the `Enter` listener of an enter animation relies on the state
+    /// set up by its `BeforeEnter` one.
+    #[test]
+    fn leave_only_keeps_the_listeners_tracking_the_element() {
+        assert_eq!(
+            listening(&only(Phase::Leave)),
+            [
+                false, false, false, false, true, true, false, false, true, true, true,
+                true
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use web_sys::MutationRecord;
+use web_sys::{IntersectionObserverEntry, MutationRecord, ResizeObserverEntry};
 
 pub trait BeforeEnter {
     fn on_before_enter(&mut self) {}
@@ -17,6 +17,17 @@ pub trait Enter {
         Duration::ZERO
     }
 
+    /// Delays of the parts of the animation entering independently, e.g. the
+    /// members of a composed one. Once the delay of a part is over,
+    /// `on_enter_part` is called with its index.
+    fn enter_delays(&self) -> Vec<Duration> {
+        vec![self.enter_delay()]
+    }
+
+    fn on_enter_part(&mut self, _part: usize) {
+        self.on_enter();
+    }
+
     fn listening_for_enter(&self) -> bool {
         false
     }
@@ -37,6 +48,15 @@ pub trait Effect {
         Duration::ZERO
     }
 
+    /// See [`Enter::enter_delays`].
+    fn effect_delays(&self) -> Vec<Duration> {
+        vec![self.effect_delay()]
+    }
+
+    fn on_effect_part(&mut self, _part: usize) {
+        self.on_effect();
+    }
+
     fn listening_for_effect(&self) -> bool {
         false
     }
@@ -58,6 +78,40 @@ pub trait ParentMutation {
     }
 }
 
+pub trait Visibility {
+    fn on_visibility(&mut self, _entry: &IntersectionObserverEntry) {}
+
+    /// Ratio of the element's visible area at which `on_visibility` is called.
+    fn visibility_threshold(&self) -> f64 {
+        0.0
+    }
+
+    /// Every ratio at which `on_visibility` is called. Composed animations
+    /// share a single observer, so the thresholds of all of them are merged
+    /// and each one may be notified at the ratios of the others as well.
+    fn visibility_thresholds(&self) -> Vec<f64> {
+        vec![self.visibility_threshold()]
+    }
+
+    /// Margin around the viewport applied when computing intersections, in
+    /// the CSS `margin` format.
+    fn visibility_root_margin(&self) -> String {
+        "0px".to_owned()
+    }
+
+    fn listening_for_visibility(&self) -> bool {
+        false
+    }
+}
+
+pub trait ElementResize {
+    fn on_element_resize(&mut self, _entry: &ResizeObserverEntry) {}
+
+    fn listening_for_element_resize(&self) -> bool {
+        false
+    }
+}
+
 pub trait EnterAnimationsFinished {
     fn on_enter_animations_finished(&mut self) {}
 
@@ -0,0 +1,150 @@
+use std::{collections::HashMap, time::Duration};
+
+use leptos::prelude::{provide_context, use_context};
+
+use crate::easing::Easing;
+
+/// Built-in animations whose defaults can be configured via
+/// [`provide_animation_config`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationKind {
+    BlurIn,
+    BlurOut,
+    ClassesIn,
+    ClassesOut,
+    Crossfade,
+    FadeIn,
+    FadeOut,
+    Flip,
+    Flip3dIn,
+    Flip3dOut,
+    FlyIn,
+    FlyOut,
+    Resize,
+    Reveal,
+    RotateIn,
+    RotateOut,
+    Shared,
+    SlideIn,
+    SlideOut,
+    ZoomIn,
+    ZoomOut,
+}
+
+/// Default options of an animation. Unset ones fall back to the next level -
+/// the defaults shared by every kind, the parent config or the built-in value.
+#[must_use]
+#[derive(Clone, Default)]
+pub struct AnimationDefaults {
+    pub(crate) duration: Option<Duration>,
+    pub(crate) easing: Option<Easing>,
+    pub(crate) delay: Option<Duration>,
+    pub(crate) enabled: Option<bool>,
+}
+
+impl AnimationDefaults {
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn easing(mut self, easing: impl Into<Easing>) -> Self {
+        self.easing = Some(easing.into());
+        self
+    }
+
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    pub const fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    fn or(self, fallback: &Self) -> Self {
+        Self {
+            duration: self.duration.or(fallback.duration),
+            easing: self.easing.or_else(|| fallback.easing.clone()),
+            delay: self.delay.or(fallback.delay),
+            enabled: self.enabled.or(fallback.enabled),
+        }
+    }
+}
+
+/// App-wide defaults of the built-in animations.
+///
+/// ```no_run
+/// provide_animation_config(
+///     AnimationConfig::default()
+///         .all(AnimationDefaults::default().easing(easing::quad_out))
+///         .kind(
+///             AnimationKind::Flip,
+///             AnimationDefaults::default().duration(Duration::from_millis(300)),
+///         ),
+/// );
+/// ```
+#[must_use]
+#[derive(Clone, Default)]
+pub struct AnimationConfig {
+    all: AnimationDefaults,
+    kinds: HashMap<AnimationKind, AnimationDefaults>,
+}
+
+impl AnimationConfig {
+    /// Defaults shared by every kind of animation.
+    pub fn all(mut self, defaults: AnimationDefaults) -> Self {
+        self.all = defaults.or(&self.all);
+        self
+    }
+
+    /// Defaults of a specific kind of animation, taking precedence over the
+    /// shared ones.
+    pub fn kind(mut self, kind: AnimationKind, defaults: AnimationDefaults) -> Self {
+        let previous = self.kinds.remove(&kind).unwrap_or_default();
+        self.kinds.insert(kind, defaults.or(&previous));
+        self
+    }
+
+    fn merge(mut self, parent: &Self) -> Self {
+        self.all = self.all.or(&parent.all);
+
+        for (kind, defaults) in &parent.kinds {
+            let own = self.kinds.remove(kind).unwrap_or_default();
+            self.kinds.insert(*kind, own.or(defaults));
+        }
+
+        self
+    }
+
+    fn resolve(&self, kind: AnimationKind) -> AnimationDefaults {
+        self.kinds
+            .get(&kind)
+            .cloned()
+            .unwrap_or_default()
+            .or(&self.all)
+    }
+}
+
+/// Provides defaults for the built-in animations created in the current
+/// component and its descendants. Options set explicitly on an animation
+/// always take precedence.
+///
+/// Nested configs extend the ones provided higher in the tree, overriding only
+/// the values they specify.
+pub fn provide_animation_config(config: AnimationConfig) {
+    let config = match use_context::<AnimationConfig>() {
+        Some(parent) => config.merge(&parent),
+        None => config,
+    };
+
+    provide_context(config);
+}
+
+/// Resolves the defaults of the given kind from the closest config.
+pub(crate) fn defaults(kind: Option<AnimationKind>) -> AnimationDefaults {
+    kind.zip(use_context::<AnimationConfig>())
+        .map(|(kind, config)| config.resolve(kind))
+        .unwrap_or_default()
+}
@@ -0,0 +1,250 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use leptos::prelude::{on_cleanup, ArcReadSignal, ArcRwSignal, Set};
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{AnimationPlayState, HtmlElement};
+
+use crate::utils::{add_oneshot_event_listener, all_finished, Cancelled, Completed};
+
+thread_local! {
+    static OBSERVED: RefCell<Vec<(HtmlElement, Weak<Inner>)>> = const {
+        RefCell::new(Vec::new())
+    };
+}
+
+/// Animation controlled by a handle along with the listener refreshing the
+/// handle once the animation is over. The listener is removed as soon as the
+/// animation is no longer tracked.
+struct Tracked {
+    animation: web_sys::Animation,
+    closure: Closure<dyn Fn()>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        for type_ in ["finish", "cancel"] {
+            _ = self.animation.remove_event_listener_with_callback(
+                type_,
+                self.closure.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+struct Inner {
+    animations: RefCell<Vec<Tracked>>,
+    play_state: ArcRwSignal<AnimationPlayState>,
+    is_running: ArcRwSignal<bool>,
+}
+
+fn aggregated_play_state(animations: &[Tracked]) -> Option<AnimationPlayState> {
+    let states = animations
+        .iter()
+        .map(|tracked| tracked.animation.play_state())
+        .collect::<Vec<_>>();
+
+    [
+        AnimationPlayState::Running,
+        AnimationPlayState::Paused,
+        AnimationPlayState::Finished,
+        AnimationPlayState::Idle,
+    ]
+    .into_iter()
+    .find(|state| states.contains(state))
+}
+
+impl Inner {
+    fn refresh(&self) {
+        let mut animations = self.animations.borrow_mut();
+
+        // keep the last known state once there is nothing left to control
+        let Some(play_state) = aggregated_play_state(&animations) else {
+            return;
+        };
+
+        animations.retain(|tracked| {
+            matches!(
+                tracked.animation.play_state(),
+                AnimationPlayState::Running | AnimationPlayState::Paused
+            )
+        });
+        drop(animations);
+
+        self.play_state.set(play_state);
+        self.is_running
+            .set(play_state == AnimationPlayState::Running);
+    }
+
+    fn add(self: &Rc<Self>, animation: &web_sys::Animation) {
+        if self
+            .animations
+            .borrow()
+            .iter()
+            .any(|tracked| &tracked.animation == animation)
+        {
+            self.refresh();
+            return;
+        }
+
+        let closure = Closure::<dyn Fn()>::new({
+            let inner = Rc::downgrade(self);
+            move || {
+                if let Some(inner) = inner.upgrade() {
+                    inner.refresh();
+                }
+            }
+        });
+        add_oneshot_event_listener(animation, "finish", &closure);
+        add_oneshot_event_listener(animation, "cancel", &closure);
+
+        self.animations.borrow_mut().push(Tracked {
+            animation: animation.clone(),
+            closure,
+        });
+
+        self.refresh();
+    }
+}
+
+/// Controls the animations spawned with
+/// [`spawn_animation`](crate::utils::spawn_animation) on an element animated
+/// via [`use_animate`](crate::use_animate).
+///
+/// Every method applies to all animations that are currently in flight on the
+/// element. Animations spawned later are picked up automatically.
+///
+/// A handle can also be created from a single animation, e.g. the one returned
+/// by `spawn_animation`, or collected from several ones in order to await
+/// their completion.
+#[derive(Clone)]
+pub struct AnimationHandle(SendWrapper<Rc<Inner>>);
+
+impl AnimationHandle {
+    pub(crate) fn new() -> Self {
+        Self(SendWrapper::new(Rc::new(Inner {
+            animations: RefCell::new(Vec::new()),
+            play_state: ArcRwSignal::new(AnimationPlayState::Idle),
+            is_running: ArcRwSignal::new(false),
+        })))
+    }
+
+    /// Starts collecting animations spawned on the element until the current
+    /// reactive owner is cleaned up.
+    pub(crate) fn observe(&self, element: &HtmlElement) {
+        let inner = Rc::downgrade(&self.0);
+
+        OBSERVED.with_borrow_mut(|observed| {
+            observed.push((element.clone(), Weak::clone(&inner)));
+        });
+
+        let inner = SendWrapper::new(inner);
+        on_cleanup(move || {
+            OBSERVED.with_borrow_mut(|observed| {
+                observed.retain(|(_, observer)| {
+                    observer.strong_count() > 0 && !observer.ptr_eq(&inner)
+                });
+            });
+        });
+    }
+
+    fn each(&self, f: impl Fn(&web_sys::Animation)) {
+        for tracked in self.0.animations.borrow().iter() {
+            f(&tracked.animation);
+        }
+
+        self.0.refresh();
+    }
+
+    /// Pauses every animation, including the ones still waiting for their
+    /// delay.
+    pub fn pause(&self) {
+        self.each(|animation| _ = animation.pause());
+    }
+
+    /// Resumes every paused animation.
+    pub fn resume(&self) {
+        self.each(|animation| _ = animation.play());
+    }
+
+    /// Stops every animation and removes its effects from the element.
+    pub fn cancel(&self) {
+        self.each(web_sys::Animation::cancel);
+    }
+
+    /// Jumps to the end of every animation.
+    pub fn finish(&self) {
+        self.each(|animation| _ = animation.finish());
+    }
+
+    /// Moves every animation to the given point of its timeline.
+    pub fn seek(&self, time: Duration) {
+        let time = time.as_secs_f64() * 1000.0;
+        self.each(|animation| animation.set_current_time(Some(time)));
+    }
+
+    /// Aggregated play state - running if at least one animation is running,
+    /// then paused, finished and idle in that order.
+    pub fn play_state(&self) -> ArcReadSignal<AnimationPlayState> {
+        self.0.play_state.read_only()
+    }
+
+    /// Whether at least one animation is running.
+    pub fn is_running(&self) -> ArcReadSignal<bool> {
+        self.0.is_running.read_only()
+    }
+
+    /// Resolves once every animation currently in flight is finished. If any
+    /// of them gets cancelled, [`Cancelled`] is returned instead.
+    pub fn finished(
+        &self,
+    ) -> impl Future<Output = Result<Completed, Cancelled>> + 'static {
+        all_finished(
+            self.0
+                .animations
+                .borrow()
+                .iter()
+                .map(|tracked| tracked.animation.clone())
+                .collect(),
+        )
+    }
+}
+
+impl From<web_sys::Animation> for AnimationHandle {
+    fn from(animation: web_sys::Animation) -> Self {
+        let handle = Self::new();
+        handle.0.add(&animation);
+        handle
+    }
+}
+
+impl FromIterator<web_sys::Animation> for AnimationHandle {
+    fn from_iter<T: IntoIterator<Item = web_sys::Animation>>(animations: T) -> Self {
+        let handle = Self::new();
+        for animation in animations {
+            handle.0.add(&animation);
+        }
+        handle
+    }
+}
+
+/// Passes a freshly spawned or resumed animation to the handles observing the
+/// element.
+pub(crate) fn track(element: &HtmlElement, animation: &web_sys::Animation) {
+    let observers = OBSERVED.with_borrow(|observed| {
+        observed
+            .iter()
+            .filter(|(observed_element, _)| observed_element == element)
+            .filter_map(|(_, inner)| inner.upgrade())
+            .collect::<Vec<_>>()
+    });
+
+    for inner in observers {
+        inner.add(animation);
+    }
+}
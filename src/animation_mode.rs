@@ -0,0 +1,36 @@
+use std::cell::Cell;
+
+/// Global switch for every animation managed by the crate, e.g. to get rid of
+/// them in end-to-end or visual regression tests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnimationMode {
+    /// Animate as usual.
+    #[default]
+    Normal,
+    /// Skip delays and complete every animation right away, still firing the
+    /// completion callbacks.
+    Instant,
+    /// Do not run animations at all.
+    Off,
+}
+
+thread_local! {
+    static MODE: Cell<AnimationMode> = const { Cell::new(AnimationMode::Normal) };
+}
+
+pub fn set_animation_mode(mode: AnimationMode) {
+    MODE.set(mode);
+}
+
+pub fn animation_mode() -> AnimationMode {
+    MODE.get()
+}
+
+pub(crate) fn is_off() -> bool {
+    animation_mode() == AnimationMode::Off
+}
+
+/// Whether delays and durations should be skipped.
+pub(crate) fn is_immediate() -> bool {
+    animation_mode() != AnimationMode::Normal
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use super::keyframed::{self, impl_preset_setters, Preset};
+use crate::AnimationKind;
+
+/// Keyframes of [`In`] and [`Out`] - blurring the element while fading it.
+#[derive(Clone, Debug)]
+pub struct Blur {
+    amount: f64,
+    opacity: f64,
+}
+
+impl Default for Blur {
+    fn default() -> Self {
+        Self {
+            amount: 8.0,
+            opacity: 0.0,
+        }
+    }
+}
+
+impl Preset for Blur {
+    const DURATION: Duration = Duration::from_millis(250);
+    const ENTER: AnimationKind = AnimationKind::BlurIn;
+    const LEAVE: AnimationKind = AnimationKind::BlurOut;
+
+    fn keyframe(&self) -> impl Fn(f64) -> Vec<(String, String)> + 'static {
+        let Self { amount, opacity } = *self;
+
+        move |away| {
+            vec![
+                ("filter".into(), format!("blur({}px)", amount * away)),
+                (
+                    "opacity".into(),
+                    (opacity - 1.0).mul_add(away, 1.0).to_string(),
+                ),
+            ]
+        }
+    }
+}
+
+impl_preset_setters!(
+    Blur;
+    /// Blur radius in pixels.
+    amount: f64,
+    /// Opacity of the element once blurred.
+    opacity: f64
+);
+
+/// Unblurs an element while fading it in when it enters the DOM.
+pub type In = keyframed::In<Blur>;
+
+/// Spawns a blurring and fading-out [`Zombie`](super::zombie::Zombie) when the
+/// element leaves the DOM.
+pub type Out = keyframed::Out<Blur>;
@@ -1,4 +1,5 @@
 mod html_element_ext;
+mod transition_timing;
 
 mod r#in;
 pub use r#in::In;
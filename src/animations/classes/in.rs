@@ -1,10 +1,10 @@
-use std::time::Duration;
+use std::{cell::Cell, time::Duration};
 
-use leptos::prelude::{ReadUntracked, WithUntracked};
+use leptos::prelude::{GetUntracked, ReadUntracked, WithUntracked};
 use reactive_stores::ArcStore;
 use web_sys::HtmlElement;
 
-use super::html_element_ext::HtmlElementExt;
+use super::{html_element_ext::HtmlElementExt, transition_timing::TransitionTiming};
 use crate::{
     animation::{listeners, Animation as AnimationTrait, Initializer},
     impl_empty_animation_listeners,
@@ -14,6 +14,10 @@ use crate::{
 
 define_options! {
     In.options;
+    @with_config ClassesIn;
+    @with_callbacks
+    on_enter_start,
+    on_enter_end;
     @with_setters
     duration: TransitionDuration = TransitionDuration::default(),
     delay: Duration = Duration::ZERO,
@@ -120,14 +124,32 @@ impl Animation {
         }
     }
 
-    fn remove_classes_on_transition_end(&mut self) {
-        let Some(classes_to_remove_on_end) = self.classes_to_remove_on_end.take() else {
+    fn remove_classes_on_transition_end(&mut self, timing: Option<TransitionTiming>) {
+        let classes_to_remove_on_end = self.classes_to_remove_on_end.take();
+        let on_enter_end = self.options.clone().on_enter_end().get_untracked();
+
+        if classes_to_remove_on_end.is_none() {
+            if let Some(timing) = timing {
+                timing.restore(&self.element);
+            }
+
+            on_enter_end.call(&self.element);
             return;
-        };
+        }
+
+        let timing = Cell::new(timing);
 
         self.options.clone().duration().with_untracked(|duration| {
             duration.on_transition_end(&self.element, move |element| {
-                element.remove_classes(&classes_to_remove_on_end);
+                if let Some(classes) = &classes_to_remove_on_end {
+                    element.remove_classes(classes);
+                }
+
+                if let Some(timing) = timing.take() {
+                    timing.restore(element);
+                }
+
+                on_enter_end.call(element);
             });
         });
     }
@@ -182,9 +204,15 @@ impl listeners::Enter for Animation {
             initial_transition_duration_priority,
         );
 
+        // before the classes are swapped, so the transitions start with it
+        let timing = TransitionTiming::apply(&self.element);
+
+        let on_enter_start = self.options.clone().on_enter_start().get_untracked();
+        on_enter_start.call(&self.element);
+
         self.remove_classes_on_enter();
         self.apply_target_classes();
-        self.remove_classes_on_transition_end();
+        self.remove_classes_on_transition_end(timing);
     }
 }
 
@@ -194,6 +222,8 @@ impl_empty_animation_listeners!(
     Effect,
     Mutation,
     ParentMutation,
+    Visibility,
+    ElementResize,
     EnterAnimationsFinished,
     MutationAnimationsFinished,
     ParentMutationAnimationsFinished,
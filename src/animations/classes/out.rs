@@ -4,7 +4,7 @@ use leptos::prelude::{GetUntracked, WithUntracked};
 use reactive_stores::ArcStore;
 use web_sys::HtmlElement;
 
-use super::html_element_ext::HtmlElementExt;
+use super::{html_element_ext::HtmlElementExt, transition_timing::TransitionTiming};
 use crate::{
     animation::{Animation, Initializer},
     animations::zombie::Zombie,
@@ -14,6 +14,9 @@ use crate::{
 
 define_options! {
     Out.options;
+    @with_config ClassesOut;
+    @with_callbacks
+    on_leave_end;
     @with_setters
     duration: TransitionDuration = TransitionDuration::default(),
     delay: Duration = Duration::ZERO,
@@ -89,6 +92,10 @@ impl Initializer for Out {
         Zombie::default()
             .enabled_signal(options.clone().enabled())
             .delay_signal(options.clone().delay())
+            .on_leave_end({
+                let on_leave_end = options.clone().on_leave_end().get_untracked();
+                move |element| on_leave_end.call(element)
+            })
             .duration_signal(options.clone().duration())
             .before_enter({
                 let initial_transition_duration = Rc::clone(&initial_transition_duration);
@@ -119,6 +126,11 @@ impl Initializer for Out {
                     &unwrap_cell(initial_transition_duration_priority),
                 );
 
+                // before the classes are swapped, so the transitions start with
+                // it; the element is removed once the transition is over, so
+                // there is nothing to restore
+                _ = TransitionTiming::apply(element);
+
                 if let Some(classes) = unwrap_cell(classes_to_remove_on_enter).as_ref() {
                     element.remove_classes(classes);
                 }
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use leptos::prelude::window;
+use web_sys::HtmlElement;
+
+use crate::{animation_mode, time_scale};
+
+const PROPERTIES: [&str; 2] = ["transition-duration", "transition-delay"];
+
+/// Parses a computed `<time>`, e.g. `0.15s`.
+fn parse_time(time: &str) -> Option<Duration> {
+    let time = time.trim();
+
+    let secs = if let Some(ms) = time.strip_suffix("ms") {
+        ms.parse::<f64>().ok()? / 1000.0
+    } else {
+        time.strip_suffix('s')?.parse::<f64>().ok()?
+    };
+
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Applies `scale` to each of the times listed in a computed value, e.g.
+/// `0.15s, 1s`.
+fn scale_times(value: &str, scale: impl Fn(Duration) -> Duration) -> Option<String> {
+    value
+        .split(',')
+        .map(|time| {
+            parse_time(time).map(|time| format!("{}s", scale(time).as_secs_f64()))
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|times| times.join(", "))
+}
+
+/// Computed value of the property with the current time scale applied to each
+/// of the listed times.
+fn scaled(element: &HtmlElement, property: &str) -> Option<String> {
+    let value = window()
+        .get_computed_style(element)
+        .ok()
+        .flatten()?
+        .get_property_value(property)
+        .ok()?;
+
+    scale_times(&value, time_scale::scaled)
+}
+
+/// Inline transition timing of an element, overridden while its classes are
+/// transitioning so that CSS transitions follow the global animation mode and
+/// time scale as well. The time scale is applied as of the start of the
+/// transition.
+///
+/// A transition keeps the timing it has been started with, so the override
+/// has to be in place before the classes starting the transitions are changed,
+/// within the same frame. The times are therefore read from the classes the
+/// element has before the change, e.g. the `active` ones.
+pub struct TransitionTiming(Vec<(&'static str, String, String)>);
+
+impl TransitionTiming {
+    /// Overrides the timing of the transitions the element is about to start,
+    /// if needed.
+    pub fn apply(element: &HtmlElement) -> Option<Self> {
+        let immediate = animation_mode::is_immediate();
+        if !immediate && !time_scale::is_scaled() {
+            return None;
+        }
+
+        let style = element.style();
+
+        Some(Self(
+            PROPERTIES
+                .into_iter()
+                .filter_map(|property| {
+                    let value = if immediate {
+                        "0s".to_owned()
+                    } else {
+                        scaled(element, property)?
+                    };
+
+                    let original = (
+                        property,
+                        style.get_property_value(property).unwrap_or_default(),
+                        style.get_property_priority(property),
+                    );
+                    _ = style.set_property_with_priority(property, &value, "important");
+                    Some(original)
+                })
+                .collect(),
+        ))
+    }
+
+    pub fn restore(self, element: &HtmlElement) {
+        let style = element.style();
+
+        for (property, value, priority) in self.0 {
+            _ = style.set_property_with_priority(property, &value, &priority);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_each_listed_time() {
+        let slowed_down = |time: Duration| time.mul_f64(2.0);
+
+        assert_eq!(
+            scale_times("0.15s, 300ms", slowed_down).as_deref(),
+            Some("0.3s, 0.6s")
+        );
+        assert_eq!(scale_times("0s", slowed_down).as_deref(), Some("0s"));
+        assert_eq!(scale_times("0.15s, auto", slowed_down), None);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc, time::Duration};
+
+use futures::channel::oneshot;
+use leptos::prelude::ReadUntracked;
+use reactive_stores::ArcStore;
+use send_wrapper::SendWrapper;
+use web_sys::{self, DomRect, HtmlElement};
+
+use crate::{
+    easing::{cubic_out, Easing},
+    spring::{resolve_timing, Spring},
+    utils::{define_options, spawn_animation},
+};
+
+mod receiver;
+pub use receiver::Receiver;
+
+mod sender;
+pub use sender::Sender;
+
+define_options! {
+    Crossfade.options;
+    @with_config Crossfade;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = Duration::from_millis(300),
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true
+}
+
+/// Pairs leaving and entering elements by their key, e.g. a todo item moved
+/// from the "pending" list to the "done" one. The leaving element fades out
+/// while flying to the position of the entering one, which flies in from the
+/// old position:
+///
+/// ```no_run
+/// let (send, receive) = crossfade();
+///
+/// view! {
+///     <For each=move || pending.get() key=|todo| todo.id let:todo>
+///         <li use:animate=(receive.key(todo.id.to_string()), send.key(todo.id.to_string()))>
+///             {todo.title}
+///         </li>
+///     </For>
+///     // and the same for the "done" list
+/// }
+/// ```
+///
+/// An element without a counterpart within a frame simply fades in or out.
+#[must_use]
+#[derive(Clone, Default)]
+pub struct Crossfade {
+    options: ArcStore<Options>,
+}
+
+impl Crossfade {
+    /// Creates the initializers of the leaving and entering elements sharing
+    /// the options.
+    pub fn split(self) -> (Sender, Receiver) {
+        let pending = Pending(SendWrapper::new(Rc::default()));
+
+        (
+            Sender::new(self.options.clone(), pending.clone()),
+            Receiver::new(self.options, pending),
+        )
+    }
+}
+
+/// Shorthand for [`Crossfade::default().split()`](Crossfade::split).
+pub fn crossfade() -> (Sender, Receiver) {
+    Crossfade::default().split()
+}
+
+/// The leaving element. Its animation is spawned by whichever of the sender
+/// and the receiver takes the entry from [`Pending`], and handed over to the
+/// zombie waiting for it, regardless of the order the frame callbacks of both
+/// run in.
+struct Leaving {
+    element: HtmlElement,
+    animation: oneshot::Sender<web_sys::Animation>,
+}
+
+impl Leaving {
+    fn spawned(self, animation: web_sys::Animation) {
+        _ = self.animation.send(animation);
+    }
+}
+
+/// Waits for the animation of the leaving element to be handed over, and then
+/// for it to finish. The element left without an animation, e.g. once its
+/// counterpart has been unmounted before pairing, is done right away.
+async fn leave<T, F>(animation: oneshot::Receiver<T>, finished: impl FnOnce(T) -> F)
+where
+    F: Future,
+{
+    if let Ok(animation) = animation.await {
+        finished(animation).await;
+    }
+}
+
+/// Elements that have left, waiting for a counterpart to enter.
+#[derive(Clone)]
+struct Pending(SendWrapper<Rc<RefCell<HashMap<String, Leaving>>>>);
+
+impl Pending {
+    fn insert(&self, key: String, leaving: Leaving) {
+        self.0.borrow_mut().insert(key, leaving);
+    }
+
+    fn take(&self, key: &str) -> Option<Leaving> {
+        self.0.borrow_mut().remove(key)
+    }
+
+    /// Takes the entry of the element, unless it has been paired already.
+    fn take_unpaired(&self, key: &str, element: &HtmlElement) -> Option<Leaving> {
+        let mut pending = self.0.borrow_mut();
+
+        if pending.get(key)?.element != *element {
+            return None;
+        }
+
+        pending.remove(key)
+    }
+}
+
+/// Flies the element from (when entering) or to (when leaving) the other
+/// position while fading it.
+fn fly(
+    element: &HtmlElement,
+    options: &ArcStore<Options>,
+    rect: &DomRect,
+    other: &DomRect,
+    entering: bool,
+) -> web_sys::Animation {
+    let (dx, dy) = (other.left() - rect.left(), other.top() - rect.top());
+    let (sx, sy) = if rect.width() == 0.0 || rect.height() == 0.0 {
+        (1.0, 1.0)
+    } else {
+        (other.width() / rect.width(), other.height() / rect.height())
+    };
+
+    let keyframe = |t: f64| {
+        let (away, opacity) = if entering { (1.0 - t, t) } else { (t, 1.0 - t) };
+        vec![
+            ("transform-origin".into(), "0 0".into()),
+            (
+                "transform".into(),
+                format!(
+                    "translate({}px,{}px) scale({},{})",
+                    dx * away,
+                    dy * away,
+                    (sx - 1.0).mul_add(away, 1.0),
+                    (sy - 1.0).mul_add(away, 1.0),
+                ),
+            ),
+            ("opacity".into(), opacity.to_string()),
+        ]
+    };
+
+    let options = options.read_untracked();
+    let (duration, easing) =
+        resolve_timing(options.spring, options.duration, &options.easing);
+
+    spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(keyframe)
+        .duration(duration)
+        .easing(easing)
+        .delay(options.delay)
+        .call()
+}
+
+/// Fades the element without a counterpart.
+fn fade(
+    element: &HtmlElement,
+    options: &ArcStore<Options>,
+    entering: bool,
+) -> web_sys::Animation {
+    let options = options.read_untracked();
+    let (duration, easing) =
+        resolve_timing(options.spring, options.duration, &options.easing);
+
+    spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(|t| {
+            let opacity = if entering { t } else { 1.0 - t };
+            vec![("opacity".into(), opacity.to_string())]
+        })
+        .duration(duration)
+        .easing(easing)
+        .delay(options.delay)
+        .call()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        pin::{pin, Pin},
+        task::{Context, Poll},
+    };
+
+    use futures::{future::ready, task::noop_waker_ref};
+
+    use super::*;
+
+    fn poll(future: Pin<&mut impl Future<Output = ()>>) -> Poll<()> {
+        future.poll(&mut Context::from_waker(noop_waker_ref()))
+    }
+
+    #[test]
+    fn waits_for_a_pairing_after_the_zombie_has_entered() {
+        let (spawned, animation) = oneshot::channel();
+        let finished = Cell::new(None);
+        let mut leave = pin!(leave(animation, |animation| {
+            finished.set(Some(animation));
+            ready(())
+        }));
+
+        assert!(poll(leave.as_mut()).is_pending());
+
+        spawned.send(1).unwrap();
+        assert!(poll(leave.as_mut()).is_ready());
+        assert_eq!(finished.get(), Some(1));
+    }
+
+    #[test]
+    fn takes_a_pairing_before_the_zombie_has_entered() {
+        let (spawned, animation) = oneshot::channel();
+        let finished = Cell::new(None);
+        spawned.send(1).unwrap();
+
+        let mut leave = pin!(leave(animation, |animation| {
+            finished.set(Some(animation));
+            ready(())
+        }));
+
+        assert!(poll(leave.as_mut()).is_ready());
+        assert_eq!(finished.get(), Some(1));
+    }
+
+    #[test]
+    fn does_not_wait_for_an_abandoned_pairing() {
+        let (spawned, animation) = oneshot::channel::<u32>();
+        drop(spawned);
+
+        let mut leave = pin!(leave(animation, |_| ready(())));
+        assert!(poll(leave.as_mut()).is_ready());
+    }
+}
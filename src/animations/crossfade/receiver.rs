@@ -0,0 +1,111 @@
+use leptos::prelude::ReadUntracked;
+use reactive_stores::ArcStore;
+use web_sys::HtmlElement;
+
+use super::{fade, fly, Options, Pending};
+use crate::{
+    animation::{listeners, Animation as AnimationTrait, Initializer},
+    impl_empty_animation_listeners,
+    utils::{measure, mutate},
+};
+
+/// Flies the element in from the position of the element that has left with
+/// the same key, see [`Crossfade`](super::Crossfade).
+#[must_use]
+#[derive(Clone)]
+pub struct Receiver {
+    key: String,
+    options: ArcStore<Options>,
+    pending: Pending,
+}
+
+impl Receiver {
+    pub(super) const fn new(options: ArcStore<Options>, pending: Pending) -> Self {
+        Self {
+            key: String::new(),
+            options,
+            pending,
+        }
+    }
+
+    /// Key pairing the element with the leaving one.
+    pub fn key(&self, key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Initializer for Receiver {
+    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
+        Animation {
+            key: self.key,
+            element,
+            options: self.options,
+            pending: self.pending,
+        }
+    }
+}
+
+struct Animation {
+    key: String,
+    element: HtmlElement,
+    options: ArcStore<Options>,
+    pending: Pending,
+}
+
+impl_empty_animation_listeners!(
+    Animation;
+    Enter,
+    ImmediateEffect,
+    Effect,
+    Mutation,
+    ParentMutation,
+    Visibility,
+    ElementResize,
+    EnterAnimationsFinished,
+    MutationAnimationsFinished,
+    ParentMutationAnimationsFinished,
+    Cleanup
+);
+
+impl listeners::BeforeEnter for Animation {
+    fn listening_for_before_enter(&self) -> bool {
+        true
+    }
+
+    fn on_before_enter(&mut self) {
+        let key = self.key.clone();
+        let element = self.element.clone();
+        let options = self.options.clone();
+        let pending = self.pending.clone();
+
+        // the leaving element might be removed later in the same tick, so wait
+        // for the next frame before deciding on the fallback
+        measure(move || {
+            let to = element.get_bounding_client_rect();
+            let paired = pending.take(&key).map(|leaving| {
+                let from = leaving.element.get_bounding_client_rect();
+                (leaving, from)
+            });
+
+            mutate(move || {
+                let Some((leaving, from)) = paired else {
+                    fade(&element, &options, true);
+                    return;
+                };
+
+                fly(&element, &options, &to, &from, true);
+                let animation = fly(&leaving.element, &options, &from, &to, false);
+                leaving.spawned(animation);
+            });
+        });
+    }
+}
+
+impl AnimationTrait for Animation {
+    fn enabled(&self) -> bool {
+        self.options.read_untracked().enabled
+    }
+}
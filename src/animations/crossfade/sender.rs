@@ -0,0 +1,78 @@
+use std::{cell::RefCell, rc::Rc};
+
+use futures::channel::oneshot;
+use reactive_stores::ArcStore;
+use web_sys::HtmlElement;
+
+use super::{fade, leave, Leaving, Options, OptionsStoreFields, Pending};
+use crate::{
+    animation::{Animation, Initializer},
+    animations::zombie::Zombie,
+    utils::all_finished,
+    TransitionDuration,
+};
+
+/// Spawns a [`Zombie`] flying to the element entering with the same key, see
+/// [`Crossfade`](super::Crossfade).
+#[must_use]
+#[derive(Clone)]
+pub struct Sender {
+    key: String,
+    options: ArcStore<Options>,
+    pending: Pending,
+}
+
+impl Sender {
+    pub(super) const fn new(options: ArcStore<Options>, pending: Pending) -> Self {
+        Self {
+            key: String::new(),
+            options,
+            pending,
+        }
+    }
+
+    /// Key pairing the element with the entering one.
+    pub fn key(&self, key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Initializer for Sender {
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        let (spawned, animation) = oneshot::channel();
+        let animation = Rc::new(RefCell::new(Some(animation)));
+
+        Zombie::default()
+            .enabled_signal(self.options.clone().enabled())
+            .duration(TransitionDuration::from(move || {
+                let animation = animation.borrow_mut().take();
+                Box::pin(async move {
+                    if let Some(animation) = animation {
+                        leave(animation, |animation| all_finished(vec![animation])).await;
+                    }
+                })
+            }))
+            .before_enter({
+                let (key, pending) = (self.key.clone(), self.pending.clone());
+                move |element| {
+                    pending.insert(
+                        key,
+                        Leaving {
+                            element: element.clone(),
+                            animation: spawned,
+                        },
+                    );
+                }
+            })
+            .enter(move |element| {
+                // paired with an entering element in the meantime otherwise
+                if let Some(leaving) = self.pending.take_unpaired(&self.key, element) {
+                    leaving.spawned(fade(element, &self.options, false));
+                }
+            })
+            .init_animation(element)
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use leptos::prelude::ReadUntracked;
+use leptos::prelude::{GetUntracked, ReadUntracked};
 use reactive_stores::ArcStore;
 use web_sys::{self, HtmlElement};
 
@@ -8,16 +8,21 @@ use crate::{
     animation::{Animation, Initializer},
     animations::zombie::Zombie,
     easing::{cubic_out, Easing},
+    spring::{resolve_timing, Spring},
     utils::{define_options, spawn_animation},
     TransitionDuration,
 };
 
 define_options! {
     Out.options;
+    @with_config FadeOut;
+    @with_callbacks
+    on_leave_end;
     @with_setters
-    easing: Easing = cubic_out,
+    easing: Easing = cubic_out.into(),
     duration: Duration = Duration::from_millis(200),
     delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
     enabled: bool = true
 }
 
@@ -33,16 +38,23 @@ impl Initializer for Out {
         Zombie::default()
             .enabled_signal(self.options.clone().enabled())
             .delay_signal(self.options.clone().delay())
+            .on_leave_end({
+                let on_leave_end = self.options.clone().on_leave_end().get_untracked();
+                move |element| on_leave_end.call(element)
+            })
             .duration(TransitionDuration::AnimationsFinished)
             .before_enter({
                 let options = self.options;
                 move |element| {
                     let options = options.read_untracked();
+                    let (duration, easing) =
+                        resolve_timing(options.spring, options.duration, &options.easing);
                     spawn_animation()
                         .element(element)
+                        .linear_keyframes(true)
                         .keyframe(|t| vec![("opacity".into(), (1.0 - t).to_string())])
-                        .duration(options.duration)
-                        .easing(options.easing)
+                        .duration(duration)
+                        .easing(easing)
                         .delay(options.delay)
                         .call();
                 }
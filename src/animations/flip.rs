@@ -6,6 +6,7 @@ use web_sys::HtmlElement;
 use crate::{
     animation::{Animation as AnimationTrait, Initializer},
     easing::{cubic_out, Easing},
+    spring::Spring,
     utils::{define_options, Trackable},
 };
 
@@ -17,10 +18,14 @@ use animation::Animation;
 
 define_options! {
     Flip.options;
+    @with_config Flip;
+    @with_callbacks
+    on_move_end;
     @with_setters
-    easing: Easing = cubic_out,
+    easing: Easing = cubic_out.into(),
     duration: Duration = Duration::default(),
     delay: time::Duration = time::Duration::ZERO,
+    spring: Option<Spring> = None,
     enabled: bool = true
 }
 
@@ -36,17 +41,36 @@ define_options! {
 ///
 /// When `some_signal` changes and influences the position of the element,
 /// the travel animation will be played.
+///
+/// Alternatively, [`Flip::observe`] reacts to any change of the element's
+/// size reported by `ResizeObserver`, moving its center from the previous
+/// position, e.g. along with
+/// [`Resize::observe`](super::resize::Resize::observe) for elements growing or
+/// shrinking due to window resizing or font loading.
+///
+/// A move interrupted by another change is retargeted - the element continues
+/// from its current visual position towards the new one.
+///
+/// If a [`Spring`] is set, it takes precedence over `duration` and `easing`.
+/// The velocity of an interrupted move is then carried over as well.
 #[must_use]
 #[derive(Clone)]
 pub struct Flip {
-    trackable: Trackable,
+    trackable: Option<Trackable>,
     options: ArcStore<Options>,
 }
 
 impl Flip {
     pub fn watch(trackable: impl Into<Trackable>) -> Self {
         Self {
-            trackable: trackable.into(),
+            trackable: Some(trackable.into()),
+            options: ArcStore::default(),
+        }
+    }
+
+    pub fn observe() -> Self {
+        Self {
+            trackable: None,
             options: ArcStore::default(),
         }
     }
@@ -1,123 +1,346 @@
-use std::time;
+use std::{cell::RefCell, rc::Rc, time};
 
 use anyhow::{anyhow, bail, Context};
-use leptos::prelude::{window, ReadUntracked};
+use leptos::{
+    prelude::{window, ReadUntracked},
+    task::spawn_local,
+};
 use reactive_stores::ArcStore;
-use web_sys::{self, DomRect};
+use web_sys::{self, AnimationPlayState, DomRect, ResizeObserverEntry};
 
 use super::{Duration, Options};
 use crate::{
-    animation::{listeners, Animation as AnimationTrait},
+    animation::{listeners, Animation as AnimationTrait, Handover},
+    easing::Easing,
     impl_empty_animation_listeners,
-    utils::{log_error, spawn_animation, Trackable},
+    reduced_motion,
+    spring::eased_velocity,
+    utils::{
+        all_finished,
+        eased_progress,
+        log_error,
+        measure,
+        mutate,
+        spawn_animation,
+        Trackable,
+    },
 };
 
 pub struct Animation {
     element: web_sys::HtmlElement,
-    trackable: Trackable,
+    trackable: Option<Trackable>,
     options: ArcStore<Options>,
-    last_rect: DomRect,
-    handle: Option<web_sys::Animation>,
+    last: Recorded,
+    measured: bool,
+    handle: Rc<RefCell<Option<InFlight>>>,
 }
 
-impl Animation {
-    pub fn new(
-        element: web_sys::HtmlElement,
-        trackable: Trackable,
-        options: ArcStore<Options>,
-    ) -> Self {
-        Self {
-            trackable,
-            last_rect: element.get_bounding_client_rect(),
-            element,
-            options,
-            handle: None,
+/// Position of the element recorded before it moves.
+#[derive(Clone)]
+struct Recorded {
+    rect: DomRect,
+    /// Offset applied by the move in progress at the time.
+    offset: (f64, f64),
+    /// Scroll position of the page at the time.
+    scroll: (f64, f64),
+}
+
+/// Measurements carried over to the animation replacing this one.
+struct Measured {
+    last: Recorded,
+    handle: Rc<RefCell<Option<InFlight>>>,
+}
+
+fn scroll_position() -> (f64, f64) {
+    let window = window();
+    (
+        window.scroll_x().unwrap_or_default(),
+        window.scroll_y().unwrap_or_default(),
+    )
+}
+
+/// The move currently being played, kept to find out its velocity once it is
+/// interrupted.
+struct InFlight {
+    animation: web_sys::Animation,
+    dx: f64,
+    dy: f64,
+    easing: Easing,
+    /// Whether the move has been replaced by a cross-fade for reduced motion,
+    /// in which case the element does not actually move.
+    crossfaded: bool,
+}
+
+impl InFlight {
+    /// Velocity of the element's offset in pixels per second.
+    fn velocity(&self) -> (f64, f64) {
+        if self.crossfaded || self.animation.play_state() != AnimationPlayState::Running {
+            return (0.0, 0.0);
         }
-    }
 
-    fn compute_transform_origin(&self) -> anyhow::Result<(f64, f64)> {
-        let Ok(Some(computed_style)) = window().get_computed_style(&self.element) else {
-            bail!("Could not get computed style");
+        let Some(timing) = self.animation.effect().map(|e| e.get_computed_timing())
+        else {
+            return (0.0, 0.0);
         };
 
-        let origin = computed_style
-            .get_property_value("transform-origin")
-            .map_err(|err| anyhow!("Could not get computed transform-origin: {err:#?}"))?
-            .replace("px", "");
-        let mut origin = origin.split(' ');
+        // a delayed move stands still until it starts
+        let Some(elapsed) = timing
+            .get_local_time()
+            .map(|time| time - timing.get_delay().unwrap_or_default())
+            .filter(|elapsed| *elapsed > 0.0)
+        else {
+            return (0.0, 0.0);
+        };
 
-        {
-            const ERR: &str = "Invalid transform-origin format";
+        // the move may have been shortened for reduced motion, and its local
+        // time runs at the playback rate set by the time scale
+        let Some(duration) = timing.get_active_duration().filter(|d| *d > 0.0) else {
+            return (0.0, 0.0);
+        };
+        let rate = eased_velocity(
+            &self.easing,
+            time::Duration::from_secs_f64(duration / 1000.0),
+            time::Duration::from_secs_f64(elapsed / 1000.0),
+        ) * self.animation.playback_rate().abs();
 
-            let ox = origin.next().context(ERR)?.parse::<f64>()?;
-            let oy = origin.next().context(ERR)?.parse::<f64>()?;
+        // the offset shrinks as the progress grows
+        (-self.dx * rate, -self.dy * rate)
+    }
 
-            Ok((ox, oy))
+    /// Translation the move currently applies to the element. It is computed
+    /// from the progress of the move rather than read from the computed style,
+    /// which includes any other transformation of the element as well.
+    fn offset(&self) -> (f64, f64) {
+        if self.crossfaded {
+            return (0.0, 0.0);
         }
+
+        eased_progress(&self.animation, &self.easing).map_or((0.0, 0.0), |progress| {
+            let rev = 1.0 - progress;
+            (self.dx * rev, self.dy * rev)
+        })
     }
+}
 
-    fn compute_distance(&self) -> anyhow::Result<(f64, f64)> {
-        let new_rect = self.element.get_bounding_client_rect();
-        let new_width = new_rect.width();
-        let new_height = new_rect.height();
+fn compute_transform_origin(
+    element: &web_sys::HtmlElement,
+) -> anyhow::Result<(f64, f64)> {
+    let Ok(Some(computed_style)) = window().get_computed_style(element) else {
+        bail!("Could not get computed style");
+    };
 
-        // if the element is no longer visible, then it could not possibly move
-        if new_width == 0.0 || new_height == 0.0 {
-            return Ok((0.0, 0.0));
-        }
+    let origin = computed_style
+        .get_property_value("transform-origin")
+        .map_err(|err| anyhow!("Could not get computed transform-origin: {err:#?}"))?
+        .replace("px", "");
+    let mut origin = origin.split(' ');
 
-        let (ox, oy) = self.compute_transform_origin()?;
+    {
+        const ERR: &str = "Invalid transform-origin format";
 
-        let dx = self.last_rect.left() + (self.last_rect.width() * ox / new_width)
-            - (new_rect.left() + ox);
+        let ox = origin.next().context(ERR)?.parse::<f64>()?;
+        let oy = origin.next().context(ERR)?.parse::<f64>()?;
+
+        Ok((ox, oy))
+    }
+}
 
-        let dy = self.last_rect.top() + (self.last_rect.height() * oy / new_height)
-            - (new_rect.top() + oy);
+fn compute_distance(
+    element: &web_sys::HtmlElement,
+    last: &Recorded,
+    offset: (f64, f64),
+) -> anyhow::Result<(f64, f64)> {
+    let new_rect = element.get_bounding_client_rect();
+    let new_width = new_rect.width();
+    let new_height = new_rect.height();
 
-        Ok((dx, dy))
+    // if the element is no longer visible, then it could not possibly move
+    if new_width == 0.0 || new_height == 0.0 {
+        return Ok((0.0, 0.0));
     }
 
-    fn compute_duration(&self, dx: f64, dy: f64) -> time::Duration {
-        match &self.options.read_untracked().duration {
-            Duration::Fixed(duration) => *duration,
-            Duration::DistanceBased(duration_fn) => {
-                duration_fn((dx * dx + dy * dy).sqrt())
-            }
-        }
+    let (ox, oy) = compute_transform_origin(element)?;
+    let (cx, cy) = offset;
+    let last_rect = &last.rect;
+
+    // scrolling the page in the meantime does not move the element
+    let (scroll_x, scroll_y) = scroll_position();
+    let (scrolled_x, scrolled_y) = (scroll_x - last.scroll.0, scroll_y - last.scroll.1);
+
+    // The interrupted move keeps running until it is replaced, so the element
+    // starts from its last layout position shifted by the current offset. The
+    // new layout position is the visual one without that offset.
+    let (last_left, last_top) = (
+        last_rect.left() - last.offset.0 + cx - scrolled_x,
+        last_rect.top() - last.offset.1 + cy - scrolled_y,
+    );
+    let (new_left, new_top) = (new_rect.left() - cx, new_rect.top() - cy);
+
+    let dx = last_left + (last_rect.width() * ox / new_width) - (new_left + ox);
+
+    let dy = last_top + (last_rect.height() * oy / new_height) - (new_top + oy);
+
+    Ok((dx, dy))
+}
+
+fn compute_duration(options: &Options, dx: f64, dy: f64) -> time::Duration {
+    match &options.duration {
+        Duration::Fixed(duration) => *duration,
+        Duration::DistanceBased(duration_fn) => duration_fn((dx * dx + dy * dy).sqrt()),
     }
+}
+
+fn compute_timing(
+    options: &Options,
+    dx: f64,
+    dy: f64,
+    velocity: (f64, f64),
+) -> (time::Duration, Easing) {
+    let Some(spring) = options.spring else {
+        return (compute_duration(options, dx, dy), options.easing.clone());
+    };
+
+    // project the velocity onto the new path, expressed in progress per second
+    let (vx, vy) = velocity;
+    let velocity = -vx.mul_add(dx, vy * dy) / dx.hypot(dy).powi(2);
+
+    spring.timing(velocity)
+}
+
+fn spawn(
+    element: &web_sys::HtmlElement,
+    options: &ArcStore<Options>,
+    dx: f64,
+    dy: f64,
+    velocity: (f64, f64),
+) -> InFlight {
+    let translate = move |t| {
+        let rev = 1.0 - t;
+        format!("{}px {}px", dx * rev, dy * rev)
+    };
 
-    fn clear_previous_animation(&mut self) {
-        if let Some(animation) = self.handle.take() {
-            animation.cancel();
+    let options = options.read_untracked();
+    let (duration, easing) = compute_timing(&options, dx, dy, velocity);
+    let handle = spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(|t| vec![("translate".into(), translate(t))])
+        .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+        .duration(duration)
+        .easing(easing.clone())
+        .delay(options.delay)
+        .call();
+
+    spawn_local({
+        let on_move_end = options.on_move_end.clone();
+        let element = element.clone();
+        let handle = handle.clone();
+        async move {
+            if all_finished(vec![handle]).await.is_ok() {
+                on_move_end.call(&element);
+            }
         }
+    });
+
+    InFlight {
+        animation: handle,
+        dx,
+        dy,
+        easing,
+        crossfaded: reduced_motion::is_crossfaded(element),
     }
+}
 
-    fn flip(&mut self) -> anyhow::Result<()> {
-        self.clear_previous_animation();
+/// Measures how far the element has moved from its last recorded position,
+/// along with the velocity of the move in progress.
+fn measure_move(
+    element: &web_sys::HtmlElement,
+    last: &Recorded,
+    handle: &RefCell<Option<InFlight>>,
+) -> Option<((f64, f64), (f64, f64))> {
+    let handle = handle.borrow();
+    let offset = handle.as_ref().map_or((0.0, 0.0), InFlight::offset);
 
-        let (dx, dy) = self.compute_distance()?;
+    let distance = match compute_distance(element, last, offset) {
+        Ok(distance) => distance,
+        Err(err) => {
+            log_error!("Failed to perform a FLIP animation: {err}");
+            return None;
+        }
+    };
 
-        if dx.abs() > f64::EPSILON || dy.abs() > f64::EPSILON {
-            let translate = move |t| {
-                let rev = 1.0 - t;
-                format!("translate({}px,{}px)", dx * rev, dy * rev)
-            };
+    let velocity = handle.as_ref().map_or((0.0, 0.0), InFlight::velocity);
+
+    Some((distance, velocity))
+}
 
-            let duration = self.compute_duration(dx, dy);
+/// Replaces the move in progress, if any, only once the new one is spawned, so
+/// the element never jumps in between.
+fn replace(
+    element: &web_sys::HtmlElement,
+    options: &ArcStore<Options>,
+    handle: &RefCell<Option<InFlight>>,
+    (dx, dy): (f64, f64),
+    velocity: (f64, f64),
+) {
+    let in_flight = (dx.abs() > f64::EPSILON || dy.abs() > f64::EPSILON)
+        .then(|| spawn(element, options, dx, dy, velocity));
 
-            let options = self.options.read_untracked();
-            let handle = spawn_animation()
-                .element(&self.element)
-                .keyframe(|t| vec![("transform".into(), translate(t))])
-                .duration(duration)
-                .easing(options.easing)
-                .delay(options.delay)
-                .call();
+    let previous = std::mem::replace(&mut *handle.borrow_mut(), in_flight);
+    if let Some(previous) = previous {
+        previous.animation.cancel();
+    }
+}
 
-            self.handle = Some(handle);
+impl Animation {
+    pub fn new(
+        element: web_sys::HtmlElement,
+        trackable: Option<Trackable>,
+        options: ArcStore<Options>,
+    ) -> Self {
+        Self {
+            trackable,
+            last: Recorded {
+                rect: element.get_bounding_client_rect(),
+                offset: (0.0, 0.0),
+                scroll: scroll_position(),
+            },
+            measured: false,
+            element,
+            options,
+            handle: Rc::default(),
         }
+    }
 
-        Ok(())
+    /// Records the layout position of the element, i.e. its visual position
+    /// without the offset applied by a move in progress.
+    fn record(&mut self) {
+        self.last = Recorded {
+            rect: self.element.get_bounding_client_rect(),
+            offset: self
+                .handle
+                .borrow()
+                .as_ref()
+                .map_or((0.0, 0.0), InFlight::offset),
+            scroll: scroll_position(),
+        };
+    }
+
+    /// Retargets the move in progress, if any, in the next frame.
+    fn flip(&self) {
+        let element = self.element.clone();
+        let options = self.options.clone();
+        let last = self.last.clone();
+        let handle = Rc::clone(&self.handle);
+
+        measure(move || {
+            let Some((distance, velocity)) = measure_move(&element, &last, &handle)
+            else {
+                return;
+            };
+
+            mutate(move || replace(&element, &options, &handle, distance, velocity));
+        });
     }
 }
 
@@ -127,6 +350,7 @@ impl_empty_animation_listeners!(
     Enter,
     Mutation,
     ParentMutation,
+    Visibility,
     EnterAnimationsFinished,
     MutationAnimationsFinished,
     ParentMutationAnimationsFinished,
@@ -135,22 +359,50 @@ impl_empty_animation_listeners!(
 
 impl listeners::ImmediateEffect for Animation {
     fn listening_for_immediate_effect(&self) -> bool {
-        true
+        self.trackable.is_some()
     }
 
     fn on_immediate_effect(&mut self) {
-        self.last_rect = self.element.get_bounding_client_rect();
+        self.record();
     }
 }
 
 impl listeners::Effect for Animation {
     fn listening_for_effect(&self) -> bool {
-        true
+        self.trackable.is_some()
     }
 
     fn on_effect(&mut self) {
-        if let Err(err) = self.flip() {
-            log_error!("Failed to perform a FLIP animation: {err}");
+        self.flip();
+    }
+}
+
+impl listeners::ElementResize for Animation {
+    fn listening_for_element_resize(&self) -> bool {
+        self.trackable.is_none()
+    }
+
+    fn on_element_resize(&mut self, _entry: &ResizeObserverEntry) {
+        // the observer reports the initial size once the element is rendered
+        if !self.measured {
+            self.measured = true;
+            self.record();
+            return;
+        }
+
+        // the layout is already up to date within the observer's callback, so
+        // the element is moved right away in the very same frame
+        let moved = measure_move(&self.element, &self.last, &self.handle);
+        self.record();
+
+        if let Some((distance, velocity)) = moved {
+            replace(
+                &self.element,
+                &self.options,
+                &self.handle,
+                distance,
+                velocity,
+            );
         }
     }
 }
@@ -161,6 +413,22 @@ impl AnimationTrait for Animation {
     }
 
     fn track(&self) {
-        self.trackable.track();
+        if let Some(trackable) = &self.trackable {
+            trackable.track();
+        }
+    }
+
+    fn hand_over(&self, handover: &mut Handover) {
+        handover.put(Measured {
+            last: self.last.clone(),
+            handle: Rc::clone(&self.handle),
+        });
+    }
+
+    fn take_over(&mut self, handover: &mut Handover) {
+        if let Some(measured) = handover.take::<Measured>() {
+            self.last = measured.last;
+            self.handle = measured.handle;
+        }
     }
 }
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use super::keyframed::{self, impl_preset_setters, Preset};
+use crate::AnimationKind;
+
+/// Axis the element flips around.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// Horizontal axis, i.e. the element flips up or down.
+    X,
+    /// Vertical axis, i.e. the element flips sideways like a card.
+    Y,
+}
+
+/// Keyframes of [`In`] and [`Out`] - turning the element around the axis in
+/// 3D. Unless the backface is visible, the element disappears once it is
+/// turned away from the viewer.
+#[derive(Clone, Debug)]
+pub struct Flip3d {
+    axis: Axis,
+    angle: f64,
+    perspective: f64,
+    backface_visible: bool,
+}
+
+impl Default for Flip3d {
+    fn default() -> Self {
+        Self {
+            axis: Axis::Y,
+            angle: 90.0,
+            perspective: 800.0,
+            backface_visible: false,
+        }
+    }
+}
+
+impl Preset for Flip3d {
+    const DURATION: Duration = Duration::from_millis(400);
+    const ENTER: AnimationKind = AnimationKind::Flip3dIn;
+    const LEAVE: AnimationKind = AnimationKind::Flip3dOut;
+
+    fn keyframe(&self) -> impl Fn(f64) -> Vec<(String, String)> + 'static {
+        let Self {
+            axis,
+            angle,
+            perspective,
+            backface_visible,
+        } = *self;
+
+        let function = match axis {
+            Axis::X => "rotateX",
+            Axis::Y => "rotateY",
+        };
+        let backface = if backface_visible {
+            "visible"
+        } else {
+            "hidden"
+        };
+
+        // The perspective has to precede the rotation, which the individual
+        // `rotate` property cannot express, and the `perspective` of the parent
+        // would turn it into the containing block of the fixed zombies. The
+        // turn is added to the own `transform` of the element instead.
+        move |away| {
+            vec![
+                ("composite".into(), "add".into()),
+                (
+                    "transform".into(),
+                    format!(
+                        "perspective({perspective}px) {function}({}deg)",
+                        angle * away
+                    ),
+                ),
+                ("backface-visibility".into(), backface.into()),
+            ]
+        }
+    }
+}
+
+impl_preset_setters!(
+    Flip3d;
+    axis: Axis,
+    /// Angle in degrees the element is turned by once away.
+    angle: f64,
+    /// Distance of the viewer from the element in pixels.
+    perspective: f64,
+    backface_visible: bool
+);
+
+/// Flips an element in around the axis in 3D when it enters the DOM.
+pub type In = keyframed::In<Flip3d>;
+
+/// Spawns a [`Zombie`](super::zombie::Zombie) flipping out around the axis in
+/// 3D when the element leaves the DOM.
+pub type Out = keyframed::Out<Flip3d>;
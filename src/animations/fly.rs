@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use super::translate::{self, Preset};
+pub use super::translate::{Direction, Distance};
+use crate::AnimationKind;
+
+/// Defaults of [`In`] and [`Out`] - moving the element a short distance while
+/// fading it.
+#[derive(Clone, Copy, Debug)]
+pub struct Fly;
+
+impl Preset for Fly {
+    const DIRECTION: Direction = Direction::Bottom;
+    const DISTANCE: Distance = Distance::Px(24.0);
+    const DURATION: Duration = Duration::from_millis(250);
+    const ENTER: AnimationKind = AnimationKind::FlyIn;
+    const LEAVE: AnimationKind = AnimationKind::FlyOut;
+    const OPACITY: f64 = 0.0;
+}
+
+/// Flies an element in from a short distance while fading it in when it
+/// enters the DOM, e.g. a toast.
+pub type In = translate::In<Fly>;
+
+/// Spawns a [`Zombie`](super::zombie::Zombie) flying away a short distance
+/// while fading out when the element leaves the DOM.
+pub type Out = translate::Out<Fly>;
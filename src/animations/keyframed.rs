@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::AnimationKind;
+
+mod r#in;
+pub use r#in::In;
+
+mod out;
+pub use out::Out;
+
+/// Keyframes of the animations turning the element away in place and back,
+/// e.g. [`blur`](super::blur) and [`rotate`](super::rotate), along with their
+/// defaults. The parameters of a preset are set via the same-named setters of
+/// [`In`] and [`Out`].
+pub trait Preset: Clone + Default + 'static {
+    /// Kind whose [config](crate::AnimationConfig) applies to [`In`].
+    const ENTER: AnimationKind;
+    /// Kind whose [config](crate::AnimationConfig) applies to [`Out`].
+    const LEAVE: AnimationKind;
+    const DURATION: Duration;
+
+    /// Keyframe of the element turned away, fully at `away = 1.0`.
+    fn keyframe(&self) -> impl Fn(f64) -> Vec<(String, String)> + 'static;
+}
+
+/// Defines setters of the parameters of a preset on both [`In`] and [`Out`].
+macro_rules! impl_preset_setters {
+    ($preset:ty; $($(#[$attr:meta])* $field:ident: $field_type:ty),+) => {
+        $crate::animations::keyframed::impl_preset_setters!(
+            @impl In $preset; $($(#[$attr])* $field: $field_type),+
+        );
+        $crate::animations::keyframed::impl_preset_setters!(
+            @impl Out $preset; $($(#[$attr])* $field: $field_type),+
+        );
+    };
+    (@impl $animation:ident $preset:ty; $($(#[$attr:meta])* $field:ident: $field_type:ty),+) => {
+        impl $crate::animations::keyframed::$animation<$preset> {
+            $(
+                $(#[$attr])*
+                pub fn $field(self, value: impl Into<$field_type>) -> Self {
+                    self.map_preset(|preset| preset.$field = value.into())
+                }
+            )+
+        }
+    };
+}
+
+pub(crate) use impl_preset_setters;
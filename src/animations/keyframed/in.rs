@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use leptos::{prelude::ReadUntracked, task::spawn_local};
+use reactive_stores::ArcStore;
+use web_sys::{self, HtmlElement};
+
+use super::Preset;
+use crate::{
+    animation::{listeners, Animation as AnimationTrait, Initializer},
+    easing::{cubic_out, Easing},
+    impl_empty_animation_listeners,
+    spring::{resolve_timing, Spring},
+    utils::{all_finished, define_options, spawn_animation},
+};
+
+define_options! {
+    In<P: Preset>.options;
+    @with_config P::ENTER;
+    @with_callbacks
+    on_enter_start,
+    on_enter_end;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = P::DURATION,
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true
+}
+
+/// Turns an element back from the keyframes of the preset when it enters the
+/// DOM, see [`blur::In`](crate::animations::blur::In),
+/// [`rotate::In`](crate::animations::rotate::In) and
+/// [`flip3d::In`](crate::animations::flip3d::In).
+#[must_use]
+#[derive(Clone)]
+pub struct In<P> {
+    options: ArcStore<Options>,
+    preset: P,
+}
+
+impl<P: Preset> Default for In<P> {
+    fn default() -> Self {
+        Self {
+            options: ArcStore::new(Options::with_defaults::<P>()),
+            preset: P::default(),
+        }
+    }
+}
+
+impl<P: Preset> In<P> {
+    /// Replaces all parameters of the preset at once.
+    pub fn preset(self, preset: P) -> Self {
+        self.map_preset(|current| *current = preset)
+    }
+
+    pub(crate) fn map_preset(mut self, f: impl FnOnce(&mut P)) -> Self {
+        f(&mut self.preset);
+        self
+    }
+}
+
+impl<P: Preset> Initializer for In<P> {
+    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
+        Animation {
+            element,
+            options: self.options,
+            keyframe: Box::new(self.preset.keyframe()),
+        }
+    }
+}
+
+struct Animation {
+    element: HtmlElement,
+    options: ArcStore<Options>,
+    keyframe: Box<dyn Fn(f64) -> Vec<(String, String)>>,
+}
+
+impl_empty_animation_listeners!(
+    Animation;
+    Enter,
+    ImmediateEffect,
+    Effect,
+    Mutation,
+    ParentMutation,
+    Visibility,
+    ElementResize,
+    EnterAnimationsFinished,
+    MutationAnimationsFinished,
+    ParentMutationAnimationsFinished,
+    Cleanup
+);
+
+impl listeners::BeforeEnter for Animation {
+    fn listening_for_before_enter(&self) -> bool {
+        true
+    }
+
+    fn on_before_enter(&mut self) {
+        let options = self.options.read_untracked();
+        let (duration, easing) =
+            resolve_timing(options.spring, options.duration, &options.easing);
+        let animation = spawn_animation()
+            .element(&self.element)
+            .linear_keyframes(true)
+            .keyframe(|t| (self.keyframe)(1.0 - t))
+            .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+            .duration(duration)
+            .easing(easing)
+            .delay(options.delay)
+            .call();
+
+        let on_enter_end = options.on_enter_end.clone();
+        let on_enter_start = options.on_enter_start.clone();
+        drop(options);
+
+        on_enter_start.call(&self.element);
+
+        let element = self.element.clone();
+        spawn_local(async move {
+            if all_finished(vec![animation]).await.is_ok() {
+                on_enter_end.call(&element);
+            }
+        });
+    }
+}
+
+impl AnimationTrait for Animation {
+    fn enabled(&self) -> bool {
+        self.options.read_untracked().enabled
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use leptos::prelude::{GetUntracked, ReadUntracked};
+use reactive_stores::ArcStore;
+use web_sys::{self, HtmlElement};
+
+use super::Preset;
+use crate::{
+    animation::{Animation, Initializer},
+    animations::zombie::Zombie,
+    easing::{cubic_out, Easing},
+    spring::{resolve_timing, Spring},
+    utils::{define_options, spawn_animation},
+    TransitionDuration,
+};
+
+define_options! {
+    Out<P: Preset>.options;
+    @with_config P::LEAVE;
+    @with_callbacks
+    on_leave_end;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = P::DURATION,
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true
+}
+
+/// Spawns a [`Zombie`] turning away with the keyframes of the preset when the
+/// element leaves the DOM, see [`blur::Out`](crate::animations::blur::Out),
+/// [`rotate::Out`](crate::animations::rotate::Out) and
+/// [`flip3d::Out`](crate::animations::flip3d::Out).
+#[must_use]
+#[derive(Clone)]
+pub struct Out<P> {
+    options: ArcStore<Options>,
+    preset: P,
+}
+
+impl<P: Preset> Default for Out<P> {
+    fn default() -> Self {
+        Self {
+            options: ArcStore::new(Options::with_defaults::<P>()),
+            preset: P::default(),
+        }
+    }
+}
+
+impl<P: Preset> Out<P> {
+    /// Replaces all parameters of the preset at once.
+    pub fn preset(self, preset: P) -> Self {
+        self.map_preset(|current| *current = preset)
+    }
+
+    pub(crate) fn map_preset(mut self, f: impl FnOnce(&mut P)) -> Self {
+        f(&mut self.preset);
+        self
+    }
+}
+
+impl<P: Preset> Initializer for Out<P> {
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        Zombie::default()
+            .enabled_signal(self.options.clone().enabled())
+            .delay_signal(self.options.clone().delay())
+            .on_leave_end({
+                let on_leave_end = self.options.clone().on_leave_end().get_untracked();
+                move |element| on_leave_end.call(element)
+            })
+            .duration(TransitionDuration::AnimationsFinished)
+            .before_enter({
+                let (options, preset) = (self.options, self.preset);
+                move |element| {
+                    let options = options.read_untracked();
+                    let (duration, easing) =
+                        resolve_timing(options.spring, options.duration, &options.easing);
+                    spawn_animation()
+                        .element(element)
+                        .linear_keyframes(true)
+                        .keyframe(preset.keyframe())
+                        .crossfade(&|t| vec![("opacity".into(), (1.0 - t).to_string())])
+                        .duration(duration)
+                        .easing(easing)
+                        .delay(options.delay)
+                        .call();
+                }
+            })
+            .init_animation(element)
+    }
+}
@@ -12,15 +12,20 @@ use animation::Animation;
 use crate::{
     animation::{Animation as AnimationTrait, Initializer},
     easing::{cubic_out, Easing},
+    spring::Spring,
     utils::{define_options, Trackable},
 };
 
 define_options! {
     Resize.options;
+    @with_config Resize;
+    @with_callbacks
+    on_resize_end;
     @with_setters
-    easing: Easing = cubic_out,
+    easing: Easing = cubic_out.into(),
     duration: Duration = Duration::default(),
     delay: time::Duration = time::Duration::ZERO,
+    spring: Option<Spring> = None,
     enabled: bool = true
 }
 
@@ -37,20 +42,31 @@ define_options! {
 /// When `some_signal` changes and influences the size of the element,
 /// the rescaling animation will be played.
 ///
+/// Alternatively, [`Resize::observe`] reacts to any change of the element's
+/// size reported by `ResizeObserver`, including the ones not caused by
+/// signals, e.g. window resizing or font loading.
+///
 /// Note that it applies a scale transformation to the element, so if its own
 /// size has changed but not the size of its content, the content will be scaled
 /// as well and may look off.
 #[must_use]
 #[derive(Clone)]
 pub struct Resize {
-    trackable: Trackable,
+    trackable: Option<Trackable>,
     options: ArcStore<Options>,
 }
 
 impl Resize {
     pub fn watch(trackable: impl Into<Trackable>) -> Self {
         Self {
-            trackable: trackable.into(),
+            trackable: Some(trackable.into()),
+            options: ArcStore::default(),
+        }
+    }
+
+    pub fn observe() -> Self {
+        Self {
+            trackable: None,
             options: ArcStore::default(),
         }
     }
@@ -1,93 +1,229 @@
-use std::time;
+use std::{cell::RefCell, rc::Rc, time};
 
-use leptos::prelude::ReadUntracked;
+use leptos::{prelude::ReadUntracked, task::spawn_local};
 use reactive_stores::ArcStore;
-use web_sys::{self, DomRect};
+use web_sys::{self, ResizeObserverEntry};
 
 use super::{Duration, Options};
 use crate::{
-    animation::{listeners, Animation as AnimationTrait},
+    animation::{listeners, Animation as AnimationTrait, Handover},
+    easing::Easing,
     impl_empty_animation_listeners,
-    utils::{spawn_animation, Trackable},
+    reduced_motion,
+    spring::resolve_timing,
+    utils::{all_finished, eased_progress, measure, mutate, spawn_animation, Trackable},
 };
 
+struct State {
+    /// Layout size of the element, i.e. without the scale of the animation in
+    /// progress.
+    last_size: (f64, f64),
+    measured: bool,
+    handle: Option<InFlight>,
+}
+
+/// The rescaling currently being played, kept to find out the scale it
+/// currently applies once it is interrupted.
+struct InFlight {
+    animation: web_sys::Animation,
+    sx: f64,
+    sy: f64,
+    easing: Easing,
+    crossfaded: bool,
+}
+
+impl InFlight {
+    fn scale(&self) -> (f64, f64) {
+        if self.crossfaded {
+            return (1.0, 1.0);
+        }
+
+        eased_progress(&self.animation, &self.easing).map_or((1.0, 1.0), |progress| {
+            (
+                progress_scale_axis(self.sx, progress),
+                progress_scale_axis(self.sy, progress),
+            )
+        })
+    }
+}
+
 pub struct Animation {
     element: web_sys::HtmlElement,
-    trackable: Trackable,
+    trackable: Option<Trackable>,
     options: ArcStore<Options>,
-    last_rect: DomRect,
-    handle: Option<web_sys::Animation>,
+    state: Rc<RefCell<State>>,
 }
 
 fn progress_scale_axis(value: f64, progress: f64) -> f64 {
     (value - 1.0) * (1.0 - progress) + 1.0
 }
 
+fn compute_scale(last_size: (f64, f64), new_size: (f64, f64)) -> Option<(f64, f64)> {
+    let (new_width, new_height) = new_size;
+
+    if new_width == 0.0 || new_height == 0.0 {
+        return None;
+    }
+
+    let (last_width, last_height) = last_size;
+
+    let sx = last_width / new_width;
+    let sy = last_height / new_height;
+
+    Some((sx, sy))
+}
+
+fn compute_duration(options: &Options, sx: f64, sy: f64) -> time::Duration {
+    match &options.duration {
+        Duration::Fixed(duration) => *duration,
+        Duration::ScaleBased(duration_fn) => duration_fn(sx.abs().max(sy.abs())),
+    }
+}
+
+fn spawn(
+    element: &web_sys::HtmlElement,
+    options: &ArcStore<Options>,
+    sx: f64,
+    sy: f64,
+) -> InFlight {
+    let scale = move |t: f64| {
+        let sx = progress_scale_axis(sx, t);
+        let sy = progress_scale_axis(sy, t);
+        format!("{sx} {sy}")
+    };
+
+    let options = options.read_untracked();
+    let (duration, easing) = resolve_timing(
+        options.spring,
+        compute_duration(&options, sx, sy),
+        &options.easing,
+    );
+    let handle = spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(|t| vec![("scale".into(), scale(t))])
+        .duration(duration)
+        .easing(easing.clone())
+        .delay(options.delay)
+        .call();
+
+    spawn_local({
+        let on_resize_end = options.on_resize_end.clone();
+        let element = element.clone();
+        let handle = handle.clone();
+        async move {
+            if all_finished(vec![handle]).await.is_ok() {
+                on_resize_end.call(&element);
+            }
+        }
+    });
+
+    InFlight {
+        animation: handle,
+        sx,
+        sy,
+        easing,
+        crossfaded: reduced_motion::is_crossfaded(element),
+    }
+}
+
+/// Layout size of the element, i.e. its visual size without the scale of the
+/// animation in progress.
+fn layout_size(element: &web_sys::HtmlElement, (cx, cy): (f64, f64)) -> (f64, f64) {
+    let rect = element.get_bounding_client_rect();
+    (rect.width() / cx, rect.height() / cy)
+}
+
 impl Animation {
     pub fn new(
         element: web_sys::HtmlElement,
-        trackable: Trackable,
+        trackable: Option<Trackable>,
         options: ArcStore<Options>,
     ) -> Self {
+        let rect = element.get_bounding_client_rect();
+
         Self {
-            last_rect: element.get_bounding_client_rect(),
+            state: Rc::new(RefCell::new(State {
+                last_size: (rect.width(), rect.height()),
+                measured: false,
+                handle: None,
+            })),
             element,
             trackable,
             options,
-            handle: None,
         }
     }
 
-    fn compute_scale(&mut self) -> Option<(f64, f64)> {
-        let new_rect = self.element.get_bounding_client_rect();
-        let new_width = new_rect.width();
-        let new_height = new_rect.height();
+    /// Records the layout size of the element before it changes.
+    fn record(&self) {
+        let mut state = self.state.borrow_mut();
+        let scale = state.handle.as_ref().map_or((1.0, 1.0), InFlight::scale);
+        state.last_size = layout_size(&self.element, scale);
+    }
 
-        if new_width == 0.0 || new_height == 0.0 {
-            return None;
-        }
+    /// Measures the element and returns its scale relative to the size it is
+    /// currently seen at, if the layout size has changed. When `record` is set,
+    /// the new layout size becomes the reference for the next change.
+    fn rescale(
+        element: &web_sys::HtmlElement,
+        state: &RefCell<State>,
+        record: bool,
+    ) -> Option<(f64, f64)> {
+        let mut state = state.borrow_mut();
 
-        let last_width = self.last_rect.width();
-        let last_height = self.last_rect.height();
+        // the in-flight rescaling keeps running until it is replaced, so the
+        // element is currently seen at its last layout size scaled by it
+        let (cx, cy) = state.handle.as_ref().map_or((1.0, 1.0), InFlight::scale);
+        let new_size = layout_size(element, (cx, cy));
+        let (last_width, last_height) = state.last_size;
 
-        let sx = last_width / new_width;
-        let sy = last_height / new_height;
+        // the observer reports the initial size once the element is rendered
+        let scale = if record && !state.measured {
+            None
+        } else {
+            compute_scale((last_width * cx, last_height * cy), new_size)
+        };
 
-        Some((sx, sy))
+        if record {
+            state.last_size = new_size;
+            state.measured = true;
+        }
+
+        scale.filter(|(sx, sy)| {
+            (sx - 1.0).abs() > f64::EPSILON || (sy - 1.0).abs() > f64::EPSILON
+        })
     }
 
-    fn compute_duration(&self, sx: f64, sy: f64) -> time::Duration {
-        match &self.options.read_untracked().duration {
-            Duration::Fixed(duration) => *duration,
-            Duration::ScaleBased(duration_fn) => duration_fn(sx.abs().max(sy.abs())),
+    /// Replaces the rescaling in progress, if any, only once the new one is
+    /// spawned, so the element never jumps in between.
+    fn replace(
+        element: &web_sys::HtmlElement,
+        options: &ArcStore<Options>,
+        state: &RefCell<State>,
+        (sx, sy): (f64, f64),
+    ) {
+        let in_flight = spawn(element, options, sx, sy);
+        let previous = state.borrow_mut().handle.replace(in_flight);
+
+        if let Some(previous) = previous {
+            previous.animation.cancel();
         }
     }
 
-    fn resize(&mut self) {
-        let Some((sx, sy)) = self.compute_scale() else {
-            return;
-        };
+    /// Measures the element in the next frame and animates it from the size it
+    /// is currently seen at.
+    fn resize(&self) {
+        let element = self.element.clone();
+        let options = self.options.clone();
+        let state = Rc::clone(&self.state);
 
-        if (sx - 1.0).abs() > f64::EPSILON || (sy - 1.0).abs() > f64::EPSILON {
-            let scale = move |t: f64| {
-                let sx = progress_scale_axis(sx, t);
-                let sy = progress_scale_axis(sy, t);
-                format!("{sx} {sy}")
+        measure(move || {
+            let Some(scale) = Self::rescale(&element, &state, false) else {
+                return;
             };
 
-            let duration = self.compute_duration(sx, sy);
-
-            let options = self.options.read_untracked();
-            let handle = spawn_animation()
-                .element(&self.element)
-                .keyframe(|t| vec![("scale".into(), scale(t))])
-                .duration(duration)
-                .easing(options.easing)
-                .delay(options.delay)
-                .call();
-
-            self.handle = Some(handle);
-        }
+            mutate(move || Self::replace(&element, &options, &state, scale));
+        });
     }
 }
 
@@ -99,23 +235,24 @@ impl_empty_animation_listeners!(
     Mutation,
     MutationAnimationsFinished,
     ParentMutation,
+    Visibility,
     ParentMutationAnimationsFinished,
     Cleanup
 );
 
 impl listeners::ImmediateEffect for Animation {
     fn listening_for_immediate_effect(&self) -> bool {
-        true
+        self.trackable.is_some()
     }
 
     fn on_immediate_effect(&mut self) {
-        self.last_rect = self.element.get_bounding_client_rect();
+        self.record();
     }
 }
 
 impl listeners::Effect for Animation {
     fn listening_for_effect(&self) -> bool {
-        true
+        self.trackable.is_some()
     }
 
     fn on_effect(&mut self) {
@@ -123,12 +260,38 @@ impl listeners::Effect for Animation {
     }
 }
 
+impl listeners::ElementResize for Animation {
+    fn listening_for_element_resize(&self) -> bool {
+        self.trackable.is_none()
+    }
+
+    fn on_element_resize(&mut self, _entry: &ResizeObserverEntry) {
+        // the layout is already up to date within the observer's callback, so
+        // the new size is read right away to animate it in the very same frame
+        if let Some(scale) = Self::rescale(&self.element, &self.state, true) {
+            Self::replace(&self.element, &self.options, &self.state, scale);
+        }
+    }
+}
+
 impl AnimationTrait for Animation {
     fn enabled(&self) -> bool {
         self.options.read_untracked().enabled
     }
 
     fn track(&self) {
-        self.trackable.track();
+        if let Some(trackable) = &self.trackable {
+            trackable.track();
+        }
+    }
+
+    fn hand_over(&self, handover: &mut Handover) {
+        handover.put(Rc::clone(&self.state));
+    }
+
+    fn take_over(&mut self, handover: &mut Handover) {
+        if let Some(state) = handover.take::<Rc<RefCell<State>>>() {
+            self.state = state;
+        }
     }
 }
@@ -0,0 +1,247 @@
+use std::{rc::Rc, time::Duration};
+
+use leptos::prelude::ReadUntracked;
+use reactive_stores::ArcStore;
+use send_wrapper::SendWrapper;
+use web_sys::{
+    self,
+    AnimationPlayState,
+    FillMode,
+    HtmlElement,
+    IntersectionObserverEntry,
+    OptionalEffectTiming,
+};
+
+use crate::{
+    animation::{listeners, Animation as AnimationTrait, Initializer},
+    easing::{cubic_out, Easing},
+    impl_empty_animation_listeners,
+    utils::{define_options, spawn_animation},
+};
+
+type KeyframeFn = SendWrapper<Rc<dyn Fn(f64) -> Vec<(String, String)>>>;
+
+/// Keyframes played when the element is revealed.
+#[derive(Clone, Default)]
+pub enum Keyframes {
+    #[default]
+    Fade,
+    Zoom,
+    Custom(KeyframeFn),
+}
+
+impl Keyframes {
+    fn at(&self, t: f64) -> Vec<(String, String)> {
+        match self {
+            Self::Fade => vec![("opacity".into(), t.to_string())],
+            Self::Zoom => vec![("scale".into(), t.to_string())],
+            Self::Custom(keyframe) => keyframe(t),
+        }
+    }
+}
+
+define_options! {
+    Reveal.options;
+    @with_config Reveal;
+    @with_setters
+    keyframes: Keyframes = Keyframes::default(),
+    easing: Easing = cubic_out.into(),
+    duration: Duration = Duration::from_millis(400),
+    delay: Duration = Duration::ZERO,
+    enabled: bool = true,
+    once: bool = true,
+    threshold: f64 = 0.0,
+    root_margin: String = "0px".to_owned()
+}
+
+/// Plays an entering animation once the element scrolls into the viewport.
+/// Until then, the element is held in the first frame of the animation.
+///
+/// ```no_run
+/// view! {
+///     <section use:animate=Reveal::zoom().threshold(0.5).once(false)>
+///         // ...
+///     </section>
+/// }
+/// ```
+///
+/// - `threshold` - ratio of the element's area that has to be visible
+/// - `root_margin` - grows or shrinks the viewport, in the CSS `margin` format
+/// - `once` - whether the animation is played only on the first intersection or
+///   every time the element becomes visible again, in which case it is played
+///   backwards whenever the element leaves the viewport
+///
+/// When composed with other animations listening for visibility changes, the
+/// root margin of the first one applies to all of them.
+#[must_use]
+#[derive(Clone, Default)]
+pub struct Reveal {
+    options: ArcStore<Options>,
+}
+
+impl Reveal {
+    pub fn fade() -> Self {
+        Self::default()
+    }
+
+    pub fn zoom() -> Self {
+        Self::default().keyframes(Keyframes::Zoom)
+    }
+
+    pub fn custom(keyframe: impl Fn(f64) -> Vec<(String, String)> + 'static) -> Self {
+        Self::default().keyframes(Keyframes::Custom(SendWrapper::new(Rc::new(keyframe))))
+    }
+}
+
+impl Initializer for Reveal {
+    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
+        Animation {
+            element,
+            options: self.options,
+            current: None,
+            revealed: false,
+        }
+    }
+}
+
+struct Animation {
+    element: HtmlElement,
+    options: ArcStore<Options>,
+    /// Animation holding, revealing or hiding the element at the moment.
+    current: Option<web_sys::Animation>,
+    revealed: bool,
+}
+
+/// Keeps the element in the state the animation ends in, so a hidden element
+/// stays hidden once the animation played backwards is over.
+fn hold(animation: &web_sys::Animation) {
+    if let Some(effect) = animation.effect() {
+        let timing = OptionalEffectTiming::new();
+        timing.set_fill(FillMode::Both);
+        _ = effect.update_timing_with_timing(&timing);
+    }
+}
+
+fn is_running(animation: &web_sys::Animation) -> bool {
+    animation.play_state() == AnimationPlayState::Running
+}
+
+impl Animation {
+    fn spawn(&self, delay: Duration) -> web_sys::Animation {
+        let options = self.options.read_untracked();
+        spawn_animation()
+            .element(&self.element)
+            .keyframe(|t| options.keyframes.at(t))
+            .linear_keyframes(!matches!(options.keyframes, Keyframes::Custom(_)))
+            .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+            .duration(options.duration)
+            .easing(options.easing.clone())
+            .delay(delay)
+            .call()
+    }
+
+    fn reveal(&mut self) {
+        match self.current.take() {
+            // turn back while still hiding
+            Some(animation) if is_running(&animation) => {
+                _ = animation.reverse();
+                self.current = Some(animation);
+            }
+            current => {
+                if let Some(animation) = current {
+                    animation.cancel();
+                }
+
+                self.current = Some(self.spawn(self.options.read_untracked().delay));
+            }
+        }
+    }
+
+    fn hide(&mut self) {
+        let animation = match self.current.take() {
+            // turn back while still revealing
+            Some(animation) if is_running(&animation) => animation,
+            current => {
+                if let Some(animation) = current {
+                    animation.cancel();
+                }
+
+                self.spawn(Duration::ZERO)
+            }
+        };
+
+        hold(&animation);
+        _ = animation.reverse();
+        self.current = Some(animation);
+    }
+}
+
+impl_empty_animation_listeners!(
+    Animation;
+    Enter,
+    ImmediateEffect,
+    Effect,
+    Mutation,
+    ParentMutation,
+    ElementResize,
+    EnterAnimationsFinished,
+    MutationAnimationsFinished,
+    ParentMutationAnimationsFinished,
+    Cleanup
+);
+
+impl listeners::BeforeEnter for Animation {
+    fn listening_for_before_enter(&self) -> bool {
+        true
+    }
+
+    fn on_before_enter(&mut self) {
+        // keep the element in its initial state until it becomes visible
+        let placeholder = self.spawn(Duration::ZERO);
+        _ = placeholder.pause();
+        self.current = Some(placeholder);
+    }
+}
+
+impl listeners::Visibility for Animation {
+    fn listening_for_visibility(&self) -> bool {
+        true
+    }
+
+    fn visibility_threshold(&self) -> f64 {
+        self.options.read_untracked().threshold
+    }
+
+    fn visibility_root_margin(&self) -> String {
+        self.options.read_untracked().root_margin.clone()
+    }
+
+    fn on_visibility(&mut self, entry: &IntersectionObserverEntry) {
+        let (threshold, once) = {
+            let options = self.options.read_untracked();
+            (options.threshold, options.once)
+        };
+
+        // the observer might be shared with other animations with different
+        // thresholds
+        let visible = entry.is_intersecting() && entry.intersection_ratio() >= threshold;
+
+        if visible == self.revealed || (self.revealed && once) {
+            return;
+        }
+
+        self.revealed = visible;
+
+        if visible {
+            self.reveal();
+        } else {
+            self.hide();
+        }
+    }
+}
+
+impl AnimationTrait for Animation {
+    fn enabled(&self) -> bool {
+        self.options.read_untracked().enabled
+    }
+}
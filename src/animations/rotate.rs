@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use super::keyframed::{self, impl_preset_setters, Preset};
+use crate::AnimationKind;
+
+/// Keyframes of [`In`] and [`Out`] - rotating the element around the
+/// `transform-origin` while fading it.
+#[derive(Clone, Debug)]
+pub struct Rotate {
+    angle: f64,
+    origin: String,
+    opacity: f64,
+}
+
+impl Default for Rotate {
+    fn default() -> Self {
+        Self {
+            angle: -90.0,
+            origin: "center".into(),
+            opacity: 0.0,
+        }
+    }
+}
+
+impl Preset for Rotate {
+    const DURATION: Duration = Duration::from_millis(300);
+    const ENTER: AnimationKind = AnimationKind::RotateIn;
+    const LEAVE: AnimationKind = AnimationKind::RotateOut;
+
+    fn keyframe(&self) -> impl Fn(f64) -> Vec<(String, String)> + 'static {
+        let Self {
+            angle,
+            origin,
+            opacity,
+        } = self.clone();
+
+        move |away| {
+            vec![
+                ("transform-origin".into(), origin.clone()),
+                ("rotate".into(), format!("{}deg", angle * away)),
+                (
+                    "opacity".into(),
+                    (opacity - 1.0).mul_add(away, 1.0).to_string(),
+                ),
+            ]
+        }
+    }
+}
+
+impl_preset_setters!(
+    Rotate;
+    /// Angle in degrees the element is rotated by once away.
+    angle: f64,
+    /// `transform-origin` the element is rotated around.
+    origin: String,
+    /// Opacity of the element once rotated away.
+    opacity: f64
+);
+
+/// Rotates an element in around the origin while fading it in when it enters
+/// the DOM.
+pub type In = keyframed::In<Rotate>;
+
+/// Spawns a [`Zombie`](super::zombie::Zombie) rotating out around the origin
+/// while fading out when the element leaves the DOM.
+pub type Out = keyframed::Out<Rotate>;
@@ -0,0 +1,332 @@
+use std::{cell::RefCell, collections::HashMap, time::Duration};
+
+use leptos::{prelude::ReadUntracked, task::spawn_local};
+use reactive_stores::ArcStore;
+use web_sys::{
+    self,
+    js_sys::Date,
+    DomRect,
+    HtmlElement,
+    MutationRecord,
+    ResizeObserverEntry,
+};
+
+use crate::{
+    animation::{listeners, Animation as AnimationTrait, Initializer},
+    easing::{cubic_out, Easing},
+    impl_empty_animation_listeners,
+    spring::{resolve_timing, Spring},
+    utils::{all_finished, define_options, measure, mutate, sleep, spawn_animation},
+};
+
+define_options! {
+    Shared.options;
+    @with_config Shared;
+    @with_callbacks
+    on_move_end;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = Duration::from_millis(300),
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true,
+    timeout: Duration = Duration::from_millis(500)
+}
+
+/// Moves an element from the last position of another element with the same
+/// id, even if it is rendered in a different parent or component, e.g. a card
+/// moved between columns, a tab underline or a hero image.
+///
+/// ```no_run
+/// view! {
+///     <For each=move || column.get() key=|card| card.id let:card>
+///         <div use:animate=Shared::id(format!("card-{}", card.id))>
+///             // ...
+///         </div>
+///     </For>
+/// }
+/// ```
+///
+/// The position of an element is remembered once it is removed, and used if
+/// another element with the same id is mounted within its `timeout`. An
+/// element still mounted at that time is moved from its current position.
+///
+/// The element is scaled along the way as well, so the elements do not have
+/// to share their size.
+#[must_use]
+#[derive(Clone)]
+pub struct Shared {
+    id: String,
+    options: ArcStore<Options>,
+}
+
+impl Shared {
+    pub fn id(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            options: ArcStore::default(),
+        }
+    }
+}
+
+impl Initializer for Shared {
+    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
+        Animation {
+            id: self.id,
+            element,
+            options: self.options,
+        }
+    }
+}
+
+/// The element currently holding the id, along with its last known position.
+struct Entry {
+    element: HtmlElement,
+    rect: Option<DomRect>,
+    /// Time the position stops being used at, once the element is removed.
+    expires_at: Option<f64>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<String, Entry>> = RefCell::new(HashMap::new());
+}
+
+/// Drops the entries of the elements removed for longer than their timeout.
+fn prune(registry: &mut HashMap<String, Entry>, now: f64) {
+    registry
+        .retain(|_, entry| entry.expires_at.is_none_or(|expires_at| now <= expires_at));
+}
+
+/// Takes over the id, returning the position to move the element from.
+fn claim(id: &str, element: &HtmlElement) -> Option<DomRect> {
+    REGISTRY.with_borrow_mut(|registry| {
+        prune(registry, Date::now());
+
+        let previous = registry.insert(
+            id.to_owned(),
+            Entry {
+                element: element.clone(),
+                rect: None,
+                expires_at: None,
+            },
+        )?;
+
+        if previous.expires_at.is_none() && previous.element.is_connected() {
+            return Some(previous.element.get_bounding_client_rect());
+        }
+
+        previous.rect
+    })
+}
+
+/// Updates the entry, unless another element has taken over the id.
+fn update(id: &str, element: &HtmlElement, f: impl FnOnce(&mut Entry)) {
+    REGISTRY.with_borrow_mut(|registry| {
+        if let Some(entry) = registry
+            .get_mut(id)
+            .filter(|entry| &entry.element == element)
+        {
+            f(entry);
+        }
+    });
+}
+
+fn spawn(
+    element: &HtmlElement,
+    options: &ArcStore<Options>,
+    from: &DomRect,
+    to: &DomRect,
+) -> Option<web_sys::Animation> {
+    if to.width() == 0.0 || to.height() == 0.0 {
+        return None;
+    }
+
+    let (dx, dy) = (from.left() - to.left(), from.top() - to.top());
+    let (sx, sy) = (from.width() / to.width(), from.height() / to.height());
+
+    let keyframe = |t: f64| {
+        let rev = 1.0 - t;
+        vec![
+            ("transform-origin".into(), "0 0".into()),
+            (
+                "transform".into(),
+                format!(
+                    "translate({}px,{}px) scale({},{})",
+                    dx * rev,
+                    dy * rev,
+                    (sx - 1.0).mul_add(rev, 1.0),
+                    (sy - 1.0).mul_add(rev, 1.0),
+                ),
+            ),
+        ]
+    };
+
+    let options = options.read_untracked();
+    let (duration, easing) =
+        resolve_timing(options.spring, options.duration, &options.easing);
+
+    Some(
+        spawn_animation()
+            .element(element)
+            .linear_keyframes(true)
+            .keyframe(keyframe)
+            .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+            .duration(duration)
+            .easing(easing)
+            .delay(options.delay)
+            .call(),
+    )
+}
+
+struct Animation {
+    id: String,
+    element: HtmlElement,
+    options: ArcStore<Options>,
+}
+
+impl Animation {
+    fn record(&self) {
+        let id = self.id.clone();
+        let element = self.element.clone();
+
+        measure(move || {
+            if element.is_connected() {
+                let rect = element.get_bounding_client_rect();
+                update(&id, &element, |entry| entry.rect = Some(rect));
+            }
+        });
+    }
+}
+
+impl_empty_animation_listeners!(
+    Animation;
+    ImmediateEffect,
+    Effect,
+    Mutation,
+    Visibility,
+    EnterAnimationsFinished,
+    MutationAnimationsFinished,
+    ParentMutationAnimationsFinished
+);
+
+impl listeners::BeforeEnter for Animation {
+    fn listening_for_before_enter(&self) -> bool {
+        true
+    }
+
+    fn on_before_enter(&mut self) {
+        let Some(from) = claim(&self.id, &self.element) else {
+            return;
+        };
+
+        let element = self.element.clone();
+        let options = self.options.clone();
+
+        measure(move || {
+            let to = element.get_bounding_client_rect();
+
+            mutate(move || {
+                let Some(animation) = spawn(&element, &options, &from, &to) else {
+                    return;
+                };
+
+                let on_move_end = options.read_untracked().on_move_end.clone();
+                spawn_local(async move {
+                    if all_finished(vec![animation]).await.is_ok() {
+                        on_move_end.call(&element);
+                    }
+                });
+            });
+        });
+    }
+}
+
+impl listeners::Enter for Animation {
+    fn listening_for_enter(&self) -> bool {
+        true
+    }
+
+    fn on_enter(&mut self) {
+        self.record();
+    }
+}
+
+impl listeners::ParentMutation for Animation {
+    fn listening_for_parent_mutation(&self) -> bool {
+        true
+    }
+
+    fn on_parent_mutation(&mut self, _mutations: &[MutationRecord]) {
+        self.record();
+    }
+}
+
+impl listeners::ElementResize for Animation {
+    fn listening_for_element_resize(&self) -> bool {
+        true
+    }
+
+    fn on_element_resize(&mut self, _entry: &ResizeObserverEntry) {
+        self.record();
+    }
+}
+
+impl listeners::Cleanup for Animation {
+    fn listening_for_cleanup(&self) -> bool {
+        true
+    }
+
+    fn on_cleanup(&mut self) {
+        let timeout = self.options.read_untracked().timeout;
+
+        update(&self.id, &self.element, |entry| {
+            if entry.element.is_connected() {
+                entry.rect = Some(entry.element.get_bounding_client_rect());
+            }
+            entry.expires_at = Some(timeout.as_secs_f64().mul_add(1000.0, Date::now()));
+        });
+
+        // the element is released even if the id is never claimed again
+        spawn_local(async move {
+            sleep(timeout).await;
+            REGISTRY.with_borrow_mut(|registry| prune(registry, Date::now()));
+        });
+    }
+}
+
+impl AnimationTrait for Animation {
+    fn enabled(&self) -> bool {
+        self.options.read_untracked().enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    use super::*;
+
+    fn entry(expires_at: Option<f64>) -> Entry {
+        Entry {
+            // never accessed when pruning
+            element: JsValue::NULL.unchecked_into(),
+            rect: None,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn prunes_only_the_expired_entries() {
+        let mut registry = HashMap::from([
+            ("mounted".to_owned(), entry(None)),
+            ("expired".to_owned(), entry(Some(1000.0))),
+            ("recent".to_owned(), entry(Some(1500.0))),
+        ]);
+
+        prune(&mut registry, 1200.0);
+
+        let mut ids = registry.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, ["mounted", "recent"]);
+    }
+}
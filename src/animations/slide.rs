@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use super::translate::{self, Preset};
+pub use super::translate::{Direction, Distance};
+use crate::AnimationKind;
+
+/// Defaults of [`In`] and [`Out`] - moving the element by its full size.
+#[derive(Clone, Copy, Debug)]
+pub struct Slide;
+
+impl Preset for Slide {
+    const DIRECTION: Direction = Direction::InlineStart;
+    const DISTANCE: Distance = Distance::Percent(100.0);
+    const DURATION: Duration = Duration::from_millis(300);
+    const ENTER: AnimationKind = AnimationKind::SlideIn;
+    const LEAVE: AnimationKind = AnimationKind::SlideOut;
+    const OPACITY: f64 = 1.0;
+}
+
+/// Slides an element in from the side when it enters the DOM, by its full size
+/// by default, e.g. a drawer.
+pub type In = translate::In<Slide>;
+
+/// Spawns a [`Zombie`](super::zombie::Zombie) sliding out to the side when the
+/// element leaves the DOM, by its full size by default.
+pub type Out = translate::Out<Slide>;
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use leptos::prelude::window;
+use web_sys::HtmlElement;
+
+use crate::{utils::measure, AnimationKind};
+
+mod r#in;
+pub use r#in::In;
+
+mod out;
+pub use out::Out;
+
+/// Defaults of the animations moving the element in and out, e.g.
+/// [`slide`](super::slide) and [`fly`](super::fly).
+pub trait Preset: Clone + 'static {
+    /// Kind whose [config](crate::AnimationConfig) applies to [`In`].
+    const ENTER: AnimationKind;
+    /// Kind whose [config](crate::AnimationConfig) applies to [`Out`].
+    const LEAVE: AnimationKind;
+    const DURATION: Duration;
+    const DIRECTION: Direction;
+    const DISTANCE: Distance;
+    /// Opacity of the element once moved away.
+    const OPACITY: f64;
+}
+
+/// Side the element enters from or leaves towards. The inline sides follow
+/// the text direction of the element, i.e. they are swapped in right-to-left
+/// layouts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    InlineStart,
+    InlineEnd,
+}
+
+impl Direction {
+    const fn is_logical(self) -> bool {
+        matches!(self, Self::InlineStart | Self::InlineEnd)
+    }
+
+    fn vector(self, element: &HtmlElement) -> (f64, f64) {
+        let (x, y) = match self {
+            Self::Top => (0.0, -1.0),
+            Self::Bottom => (0.0, 1.0),
+            Self::Left | Self::InlineStart => (-1.0, 0.0),
+            Self::Right | Self::InlineEnd => (1.0, 0.0),
+        };
+
+        if self.is_logical() && is_rtl(element) {
+            (-x, y)
+        } else {
+            (x, y)
+        }
+    }
+}
+
+/// Distance the element travels, either in pixels or in percents of its own
+/// size along the direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distance {
+    Px(f64),
+    Percent(f64),
+}
+
+impl Distance {
+    fn css(self, factor: f64) -> String {
+        match self {
+            Self::Px(px) => format!("{}px", px * factor),
+            Self::Percent(percent) => format!("{}%", percent * factor),
+        }
+    }
+}
+
+fn is_rtl(element: &HtmlElement) -> bool {
+    window()
+        .get_computed_style(element)
+        .ok()
+        .flatten()
+        .and_then(|style| style.get_property_value("direction").ok())
+        .is_some_and(|direction| direction == "rtl")
+}
+
+/// Runs the task once the direction can be resolved - the text direction of a
+/// logical one is known only after the element has been inserted, so the task
+/// is deferred to the next frame then.
+pub(super) fn resolve(direction: Direction, task: impl FnOnce() + 'static) {
+    if direction.is_logical() {
+        measure(task);
+    } else {
+        task();
+    }
+}
+
+/// Keyframe of the element moved away towards the direction, fully at
+/// `away = 1.0`, and faded to the opacity along the way.
+pub(super) fn keyframe(
+    element: &HtmlElement,
+    direction: Direction,
+    distance: Distance,
+    opacity: f64,
+) -> impl Fn(f64) -> Vec<(String, String)> {
+    let (x, y) = direction.vector(element);
+
+    move |away| {
+        vec![
+            (
+                "translate".into(),
+                format!("{} {}", distance.css(x * away), distance.css(y * away)),
+            ),
+            (
+                "opacity".into(),
+                (opacity - 1.0).mul_add(away, 1.0).to_string(),
+            ),
+        ]
+    }
+}
@@ -0,0 +1,134 @@
+use std::{marker::PhantomData, time::Duration};
+
+use leptos::{prelude::ReadUntracked, task::spawn_local};
+use reactive_stores::ArcStore;
+use web_sys::{self, HtmlElement};
+
+use super::{Direction, Distance, Preset};
+use crate::{
+    animation::{listeners, Animation as AnimationTrait, Initializer},
+    animations::translate,
+    easing::{cubic_out, Easing},
+    impl_empty_animation_listeners,
+    spring::{resolve_timing, Spring},
+    utils::{all_finished, define_options, spawn_animation},
+};
+
+define_options! {
+    In<P: Preset>.options;
+    @with_config P::ENTER;
+    @with_callbacks
+    on_enter_start,
+    on_enter_end;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = P::DURATION,
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true,
+    direction: Direction = P::DIRECTION,
+    distance: Distance = P::DISTANCE,
+    opacity: f64 = P::OPACITY
+}
+
+/// Moves an element in from the side when it enters the DOM, with the defaults
+/// of the preset, see [`slide::In`](crate::animations::slide::In) and
+/// [`fly::In`](crate::animations::fly::In).
+#[must_use]
+#[derive(Clone)]
+pub struct In<P> {
+    options: ArcStore<Options>,
+    preset: PhantomData<P>,
+}
+
+impl<P: Preset> Default for In<P> {
+    fn default() -> Self {
+        Self {
+            options: ArcStore::new(Options::with_defaults::<P>()),
+            preset: PhantomData,
+        }
+    }
+}
+
+impl<P: Preset> Initializer for In<P> {
+    fn init_animation(self, element: HtmlElement) -> impl AnimationTrait {
+        Animation {
+            element,
+            options: self.options,
+        }
+    }
+}
+
+struct Animation {
+    element: HtmlElement,
+    options: ArcStore<Options>,
+}
+
+impl_empty_animation_listeners!(
+    Animation;
+    Enter,
+    ImmediateEffect,
+    Effect,
+    Mutation,
+    ParentMutation,
+    Visibility,
+    ElementResize,
+    EnterAnimationsFinished,
+    MutationAnimationsFinished,
+    ParentMutationAnimationsFinished,
+    Cleanup
+);
+
+fn spawn(element: &HtmlElement, options: &ArcStore<Options>) {
+    let options = options.read_untracked();
+    let (duration, easing) =
+        resolve_timing(options.spring, options.duration, &options.easing);
+    let keyframe = translate::keyframe(
+        element,
+        options.direction,
+        options.distance,
+        options.opacity,
+    );
+    let animation = spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(|t| keyframe(1.0 - t))
+        .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+        .duration(duration)
+        .easing(easing)
+        .delay(options.delay)
+        .call();
+
+    let on_enter_end = options.on_enter_end.clone();
+    let on_enter_start = options.on_enter_start.clone();
+    drop(options);
+
+    on_enter_start.call(element);
+
+    let element = element.clone();
+    spawn_local(async move {
+        if all_finished(vec![animation]).await.is_ok() {
+            on_enter_end.call(&element);
+        }
+    });
+}
+
+impl listeners::BeforeEnter for Animation {
+    fn listening_for_before_enter(&self) -> bool {
+        true
+    }
+
+    fn on_before_enter(&mut self) {
+        let element = self.element.clone();
+        let options = self.options.clone();
+        let direction = options.read_untracked().direction;
+
+        translate::resolve(direction, move || spawn(&element, &options));
+    }
+}
+
+impl AnimationTrait for Animation {
+    fn enabled(&self) -> bool {
+        self.options.read_untracked().enabled
+    }
+}
@@ -0,0 +1,95 @@
+use std::{marker::PhantomData, time::Duration};
+
+use leptos::prelude::{GetUntracked, ReadUntracked};
+use reactive_stores::ArcStore;
+use web_sys::{self, HtmlElement};
+
+use super::{Direction, Distance, Preset};
+use crate::{
+    animation::{Animation, Initializer},
+    animations::{translate, zombie::Zombie},
+    easing::{cubic_out, Easing},
+    spring::{resolve_timing, Spring},
+    utils::{define_options, spawn_animation},
+    TransitionDuration,
+};
+
+define_options! {
+    Out<P: Preset>.options;
+    @with_config P::LEAVE;
+    @with_callbacks
+    on_leave_end;
+    @with_setters
+    easing: Easing = cubic_out.into(),
+    duration: Duration = P::DURATION,
+    delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
+    enabled: bool = true,
+    direction: Direction = P::DIRECTION,
+    distance: Distance = P::DISTANCE,
+    opacity: f64 = P::OPACITY
+}
+
+/// Spawns a [`Zombie`] moving out to the side when the element leaves the DOM,
+/// with the defaults of the preset, see
+/// [`slide::Out`](crate::animations::slide::Out) and
+/// [`fly::Out`](crate::animations::fly::Out).
+#[must_use]
+#[derive(Clone)]
+pub struct Out<P> {
+    options: ArcStore<Options>,
+    preset: PhantomData<P>,
+}
+
+impl<P: Preset> Default for Out<P> {
+    fn default() -> Self {
+        Self {
+            options: ArcStore::new(Options::with_defaults::<P>()),
+            preset: PhantomData,
+        }
+    }
+}
+
+fn spawn(element: &HtmlElement, options: &ArcStore<Options>) {
+    let options = options.read_untracked();
+    let (duration, easing) =
+        resolve_timing(options.spring, options.duration, &options.easing);
+    let keyframe = translate::keyframe(
+        element,
+        options.direction,
+        options.distance,
+        options.opacity,
+    );
+    spawn_animation()
+        .element(element)
+        .linear_keyframes(true)
+        .keyframe(keyframe)
+        .crossfade(&|t| vec![("opacity".into(), (1.0 - t).to_string())])
+        .duration(duration)
+        .easing(easing)
+        .delay(options.delay)
+        .call();
+}
+
+impl<P: Preset> Initializer for Out<P> {
+    fn init_animation(self, element: HtmlElement) -> impl Animation {
+        Zombie::default()
+            .enabled_signal(self.options.clone().enabled())
+            .delay_signal(self.options.clone().delay())
+            .on_leave_end({
+                let on_leave_end = self.options.clone().on_leave_end().get_untracked();
+                move |element| on_leave_end.call(element)
+            })
+            .duration(TransitionDuration::AnimationsFinished)
+            .before_enter({
+                let options = self.options;
+                move |element| {
+                    let element = element.clone();
+                    let direction = options.read_untracked().direction;
+
+                    translate::resolve(direction, move || spawn(&element, &options));
+                }
+            })
+            .init_animation(element)
+    }
+}
@@ -1,4 +1,4 @@
-use std::{future::Future, time::Duration};
+use std::{cell::RefCell, future::Future, rc::Rc, time::Duration};
 
 use anyhow::{bail, Context};
 use futures::join;
@@ -13,11 +13,18 @@ use web_sys::{DomRect, Element, HtmlElement, MutationRecord};
 
 use crate::{
     animation::{listeners, Animation as AnimationTrait, Initializer},
+    animation_mode,
     impl_empty_animation_listeners,
-    utils::{animation_frame, define_options, log_error, sleep},
+    reduced_motion,
+    time_scale,
+    timing_scope,
+    utils::{animation_frame, define_options, log_error, measure, sleep},
     TransitionDuration,
 };
 
+/// Attribute marking the elements reappended by a zombie.
+pub(crate) const ZOMBIE_ATTR: &str = "zombie";
+
 type Cb = SendWrapper<Box<dyn FnOnce(&HtmlElement)>>;
 
 fn empty_cb() -> Cb {
@@ -28,6 +35,8 @@ define_options! {
     Zombie.options;
     before_enter: Cb = empty_cb(),
     enter: Cb = empty_cb(),
+    @with_callbacks
+    on_leave_end;
     @with_setters
     duration: TransitionDuration = TransitionDuration::default(),
     delay: Duration = Duration::ZERO,
@@ -76,18 +85,34 @@ impl Initializer for Zombie {
 struct Animation {
     options: ArcStore<Options>,
     element: HtmlElement,
-    parent_element: Option<Element>,
-    last_rect: DomRect,
+    parent_element: Rc<RefCell<Option<Element>>>,
+    last_rect: Rc<RefCell<DomRect>>,
 }
 
 impl Animation {
     fn new(element: HtmlElement, options: ArcStore<Options>) -> Self {
-        Self {
+        let animation = Self {
             options,
-            last_rect: element.get_bounding_client_rect(),
+            last_rect: Rc::new(RefCell::new(element.get_bounding_client_rect())),
             element,
-            parent_element: None,
-        }
+            parent_element: Rc::default(),
+        };
+
+        // The position is first recorded once the element is inserted, rather
+        // than in the `Enter` listener, so it is known even if only the leave
+        // phase is kept, see `InitializerExt::leave_only`. The listeners keep
+        // it up to date afterwards.
+        spawn_local({
+            let element = animation.element.clone();
+            let parent_element = Rc::clone(&animation.parent_element);
+            let last_rect = Rc::clone(&animation.last_rect);
+            async move {
+                animation_frame().await;
+                record(&element, &parent_element, &last_rect);
+            }
+        });
+
+        animation
     }
 
     fn fix_position(&self) {
@@ -98,10 +123,11 @@ impl Animation {
             .expect("document to be Element")
             .get_bounding_client_rect();
 
-        let top = self.last_rect.top() - document_rect.top();
-        let left = self.last_rect.left() - document_rect.left();
-        let width = self.last_rect.width();
-        let height = self.last_rect.height();
+        let last_rect = self.last_rect.borrow();
+        let top = last_rect.top() - document_rect.top();
+        let left = last_rect.left() - document_rect.left();
+        let width = last_rect.width();
+        let height = last_rect.height();
 
         let style = self.element.style();
         _ = style.set_property_with_priority("position", "fixed", IMPORTANT);
@@ -118,23 +144,21 @@ impl Animation {
     }
 
     fn delay_fut(&self) -> impl Future<Output = ()> {
-        let delay = self.options.clone().delay().get_untracked();
+        let delay =
+            timing_scope::current().delay(self.options.clone().delay().get_untracked());
 
         async move {
-            if !delay.is_zero() {
-                sleep(delay).await;
+            if !delay.is_zero() && !animation_mode::is_immediate() {
+                sleep(time_scale::scaled(delay)).await;
             }
         }
     }
 
-    fn record(&mut self) {
-        self.last_rect = self.element.get_bounding_client_rect();
-        self.parent_element = self.element.parent_element();
+    fn record(&self) {
+        record(&self.element, &self.parent_element, &self.last_rect);
     }
 
     fn is_element_already_connected(&mut self) -> anyhow::Result<bool> {
-        const ZOMBIE_ATTR: &str = "zombie";
-
         // The element could be already re-appended by another zombie
         let mut already_connected = self.element.is_connected();
 
@@ -148,9 +172,12 @@ impl Animation {
                 bail!("Could not clone node");
             };
 
+            reduced_motion::inherit(&self.element, &element);
             self.element = element;
             already_connected = false;
-        } else if self.element.set_attribute(ZOMBIE_ATTR, "").is_err() {
+        }
+
+        if self.element.set_attribute(ZOMBIE_ATTR, "").is_err() {
             bail!("Could not set zombie attribute");
         }
 
@@ -176,7 +203,8 @@ impl Animation {
 
         let parent_element = self
             .parent_element
-            .as_ref()
+            .borrow()
+            .clone()
             .context("Parent element not found")?;
 
         if !parent_element.is_connected() {
@@ -216,6 +244,9 @@ impl Animation {
             let delay_fut = self.delay_fut();
             let element = self.element.clone();
             let enter = self.options.clone().enter();
+            let on_leave_end = self.options.clone().on_leave_end().get_untracked();
+            // the additional delay is already awaited along with the own one
+            let timing = timing_scope::current().without_delay();
 
             async move {
                 join!(animation_frame(), delay_fut);
@@ -228,9 +259,11 @@ impl Animation {
                     );
                 }
 
-                std::mem::replace(&mut *enter.write(), empty_cb()).take()(&element);
-                duration.on_transition_end(&element, |element| {
+                let enter = std::mem::replace(&mut *enter.write(), empty_cb()).take();
+                timing_scope::within(timing, || enter(&element));
+                duration.on_transition_end(&element, move |element| {
                     element.remove();
+                    on_leave_end.call(element);
                 });
             }
         });
@@ -239,23 +272,39 @@ impl Animation {
     }
 }
 
+/// Records the position of the element while it is still in place.
+fn record(
+    element: &HtmlElement,
+    parent_element: &RefCell<Option<Element>>,
+    last_rect: &Rc<RefCell<DomRect>>,
+) {
+    // the element might have been removed already, keep its last known
+    // parent then
+    if let Some(parent) = element.parent_element() {
+        *parent_element.borrow_mut() = Some(parent);
+    }
+
+    let element = element.clone();
+    let last_rect = Rc::clone(last_rect);
+    measure(move || {
+        // the element might have been removed in the meantime, keep its last
+        // known position then
+        if element.is_connected() {
+            *last_rect.borrow_mut() = element.get_bounding_client_rect();
+        }
+    });
+}
+
 impl_empty_animation_listeners!(
     Animation;
     BeforeEnter,
+    Enter,
     ImmediateEffect,
-    Effect
+    Effect,
+    Visibility,
+    ElementResize
 );
 
-impl listeners::Enter for Animation {
-    fn listening_for_enter(&self) -> bool {
-        true
-    }
-
-    fn on_enter(&mut self) {
-        self.record();
-    }
-}
-
 impl listeners::Mutation for Animation {
     fn listening_for_mutation(&self) -> bool {
         true
@@ -312,6 +361,13 @@ impl listeners::Cleanup for Animation {
     }
 
     fn on_cleanup(&mut self) {
+        // a read deferred by `record` might not have run yet, so the last
+        // position is taken right away while the element is still in place
+        if self.element.is_connected() {
+            *self.last_rect.borrow_mut() = self.element.get_bounding_client_rect();
+            *self.parent_element.borrow_mut() = self.element.parent_element();
+        }
+
         if let Err(err) = self.spawn_zombie() {
             log_error!("Could not spawn zombie: {err}");
         }
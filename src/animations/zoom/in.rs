@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use leptos::prelude::ReadUntracked;
+use leptos::{prelude::ReadUntracked, task::spawn_local};
 use reactive_stores::ArcStore;
 use web_sys::{self, HtmlElement};
 
@@ -8,15 +8,21 @@ use crate::{
     animation::{listeners, Animation as AnimationTrait, Initializer},
     easing::{cubic_out, Easing},
     impl_empty_animation_listeners,
-    utils::{define_options, spawn_animation},
+    spring::{resolve_timing, Spring},
+    utils::{all_finished, define_options, spawn_animation},
 };
 
 define_options! {
     In.options;
+    @with_config ZoomIn;
+    @with_callbacks
+    on_enter_start,
+    on_enter_end;
     @with_setters
-    easing: Easing = cubic_out,
+    easing: Easing = cubic_out.into(),
     duration: Duration = Duration::from_millis(200),
     delay: Duration = Duration::ZERO,
+    spring: Option<Spring> = None,
     enabled: bool = true
 }
 
@@ -48,6 +54,8 @@ impl_empty_animation_listeners!(
     Effect,
     Mutation,
     ParentMutation,
+    Visibility,
+    ElementResize,
     EnterAnimationsFinished,
     MutationAnimationsFinished,
     ParentMutationAnimationsFinished,
@@ -61,13 +69,30 @@ impl listeners::BeforeEnter for Animation {
 
     fn on_before_enter(&mut self) {
         let options = self.options.read_untracked();
-        spawn_animation()
+        let (duration, easing) =
+            resolve_timing(options.spring, options.duration, &options.easing);
+        let animation = spawn_animation()
             .element(&self.element)
+            .linear_keyframes(true)
             .keyframe(|t| vec![("scale".into(), t.to_string())])
-            .duration(options.duration)
-            .easing(options.easing)
+            .crossfade(&|t| vec![("opacity".into(), t.to_string())])
+            .duration(duration)
+            .easing(easing)
             .delay(options.delay)
             .call();
+
+        let on_enter_end = options.on_enter_end.clone();
+        let on_enter_start = options.on_enter_start.clone();
+        drop(options);
+
+        on_enter_start.call(&self.element);
+
+        let element = self.element.clone();
+        spawn_local(async move {
+            if all_finished(vec![animation]).await.is_ok() {
+                on_enter_end.call(&element);
+            }
+        });
     }
 }
 
@@ -1,6 +1,250 @@
-use std::f64::consts::PI;
+use std::{any::TypeId, f64::consts::PI, rc::Rc};
 
-pub type Easing = fn(f64) -> f64;
+use send_wrapper::SendWrapper;
+use web_sys::css;
+
+/// Upper bound of the `linear()` stops a sampled easing is passed with.
+const MAX_LINEAR_STOPS: usize = 64;
+
+thread_local! {
+    static LINEAR_EASING_SUPPORTED: bool =
+        css::supports_with_value("animation-timing-function", "linear(0, 1)")
+            .unwrap_or_default();
+}
+
+/// Step position of [`Easing::steps`], mirroring the CSS `<step-position>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Jump {
+    Start,
+    #[default]
+    End,
+    None,
+    Both,
+}
+
+impl Jump {
+    const fn as_css(self) -> &'static str {
+        match self {
+            Self::Start => "jump-start",
+            Self::End => "jump-end",
+            Self::None => "jump-none",
+            Self::Both => "jump-both",
+        }
+    }
+}
+
+type EasingFn = SendWrapper<Rc<dyn Fn(f64) -> f64>>;
+
+#[derive(Clone)]
+enum Kind {
+    Function(EasingFn),
+    CubicBezier(f64, f64, f64, f64),
+    Steps(u32, Jump),
+}
+
+/// Maps the linear progress of an animation to the eased one.
+///
+/// Any `Fn(f64) -> f64`, including the functions defined in this module,
+/// converts into an easing. Parametrised curves are available as constructors,
+/// and the ones having a CSS counterpart are passed to the browser as is.
+#[derive(Clone)]
+pub struct Easing(Kind);
+
+impl Easing {
+    pub fn custom(easing: impl Fn(f64) -> f64 + 'static) -> Self {
+        Self(Kind::Function(SendWrapper::new(Rc::new(easing))))
+    }
+
+    /// Equivalent of CSS `cubic-bezier()`. The x coordinates are clamped to
+    /// the `[0, 1]` range.
+    pub const fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self(Kind::CubicBezier(
+            x1.clamp(0.0, 1.0),
+            y1,
+            x2.clamp(0.0, 1.0),
+            y2,
+        ))
+    }
+
+    /// Equivalent of CSS `steps()`. [`Jump::None`] needs at least two steps to
+    /// get from the start to the end, the other positions at least one.
+    pub fn steps(steps: u32, jump: Jump) -> Self {
+        let min = if jump == Jump::None { 2 } else { 1 };
+
+        Self(Kind::Steps(steps.max(min), jump))
+    }
+
+    /// [`back_out`] with a custom overshoot, `1.70158` by default.
+    pub fn back_out(overshoot: f64) -> Self {
+        Self::custom(move |t| {
+            (t - 1.0) * (t - 1.0) * ((overshoot + 1.0) * (t - 1.0) + overshoot) + 1.0
+        })
+    }
+
+    /// Elastic curve with a custom amplitude (at least 1) and period, both
+    /// relative to the animated distance.
+    pub fn elastic_out(amplitude: f64, period: f64) -> Self {
+        let amplitude = amplitude.max(1.0);
+        let period = period.max(f64::EPSILON);
+        let shift = period / (2.0 * PI) * (1.0 / amplitude).asin();
+
+        Self::custom(move |t| {
+            amplitude * 2.0_f64.powf(-10.0 * t) * ((t - shift) * 2.0 * PI / period).sin()
+                + 1.0
+        })
+    }
+
+    pub fn ease(&self, t: f64) -> f64 {
+        match &self.0 {
+            Kind::Function(easing) => easing(t),
+            Kind::CubicBezier(x1, y1, x2, y2) => cubic_bezier_at(t, *x1, *y1, *x2, *y2),
+            Kind::Steps(steps, jump) => steps_at(t, *steps, *jump),
+        }
+    }
+
+    /// Returns the CSS easing equivalent. Arbitrary functions are sampled into
+    /// the given number of `linear()` segments, up to [`MAX_LINEAR_STOPS`],
+    /// unless the browser does not support them.
+    pub(crate) fn to_css(&self, num_steps: usize) -> Option<String> {
+        match &self.0 {
+            Kind::CubicBezier(x1, y1, x2, y2) => {
+                Some(format!("cubic-bezier({x1}, {y1}, {x2}, {y2})"))
+            }
+            Kind::Steps(steps, jump) => {
+                Some(format!("steps({steps}, {})", jump.as_css()))
+            }
+            Kind::Function(_) if LINEAR_EASING_SUPPORTED.with(|supported| *supported) => {
+                Some(self.to_css_linear(num_steps))
+            }
+            Kind::Function(_) => None,
+        }
+    }
+
+    fn to_css_linear(&self, num_steps: usize) -> String {
+        let num_steps = num_steps.clamp(1, MAX_LINEAR_STOPS);
+        let stops = (0..=num_steps)
+            .map(|step| {
+                let t = step as f64 / num_steps as f64;
+                (t, self.ease(t))
+            })
+            .collect::<Vec<_>>();
+
+        if stops
+            .iter()
+            .all(|(t, value)| (t - value).abs() < f64::EPSILON)
+        {
+            return "linear".to_owned();
+        }
+
+        let stops = stops
+            .into_iter()
+            .map(|(_, value)| value.to_string())
+            .collect::<Vec<_>>();
+
+        format!("linear({})", stops.join(", "))
+    }
+}
+
+impl<F> From<F> for Easing
+where
+    F: Fn(f64) -> f64 + 'static,
+{
+    fn from(easing: F) -> Self {
+        as_cubic_bezier::<F>().unwrap_or_else(|| Self::custom(easing))
+    }
+}
+
+const fn type_id_of<T: 'static>(_: &T) -> TypeId {
+    TypeId::of::<T>()
+}
+
+/// Curve of the functions of this module that are cubic polynomials, which
+/// makes them exact cubic Bézier curves with the control points at a third of
+/// the way. They can be passed to the browser as `cubic-bezier()` then.
+fn as_cubic_bezier<F: 'static>() -> Option<Easing> {
+    const THIRD: f64 = 1.0 / 3.0;
+    const TWO_THIRDS: f64 = 2.0 / 3.0;
+
+    [
+        (type_id_of(&linear), (0.0, 0.0, 1.0, 1.0)),
+        (type_id_of(&quad_in), (THIRD, 0.0, TWO_THIRDS, THIRD)),
+        (type_id_of(&quad_out), (THIRD, TWO_THIRDS, TWO_THIRDS, 1.0)),
+        (type_id_of(&cubic_in), (THIRD, 0.0, TWO_THIRDS, 0.0)),
+        (type_id_of(&cubic_out), (THIRD, 1.0, TWO_THIRDS, 1.0)),
+    ]
+    .into_iter()
+    .find(|(id, _)| *id == TypeId::of::<F>())
+    .map(|(_, (x1, y1, x2, y2))| Easing::cubic_bezier(x1, y1, x2, y2))
+}
+
+fn cubic_bezier_at(t: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    fn bezier(s: f64, p1: f64, p2: f64) -> f64 {
+        let rev = 1.0 - s;
+        3.0 * rev * rev * s * p1 + 3.0 * rev * s * s * p2 + s * s * s
+    }
+
+    fn bezier_slope(s: f64, p1: f64, p2: f64) -> f64 {
+        let rev = 1.0 - s;
+        3.0 * rev * rev * p1 + 6.0 * rev * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    }
+
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+
+    // find the curve parameter for the given x with Newton's method, falling
+    // back to bisection where the slope is too flat
+    let mut s = t;
+    for _ in 0..8 {
+        let slope = bezier_slope(s, x1, x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+
+        let x = bezier(s, x1, x2) - t;
+        if x.abs() < 1e-7 {
+            return bezier(s, y1, y2);
+        }
+
+        s -= x / slope;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..32 {
+        let x = bezier(s, x1, x2);
+        if (x - t).abs() < 1e-7 {
+            break;
+        }
+
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+
+        s = f64::midpoint(low, high);
+    }
+
+    bezier(s, y1, y2)
+}
+
+fn steps_at(t: f64, steps: u32, jump: Jump) -> f64 {
+    let steps = f64::from(steps);
+
+    let mut step = (t * steps).floor();
+    if matches!(jump, Jump::Start | Jump::Both) {
+        step += 1.0;
+    }
+
+    let jumps = match jump {
+        Jump::Start | Jump::End => steps,
+        Jump::None => (steps - 1.0).max(1.0),
+        Jump::Both => steps + 1.0,
+    };
+
+    (step / jumps).clamp(0.0, 1.0)
+}
 
 // adopted from https://github.com/mattdesl/eases
 
@@ -199,3 +443,70 @@ pub fn sine_in(t: f64) -> f64 {
 pub fn sine_out(t: f64) -> f64 {
     (PI * 0.5 * t).sin()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_to_css() {
+        assert_eq!(
+            Easing::steps(4, Jump::Start).to_css(0).as_deref(),
+            Some("steps(4, jump-start)")
+        );
+        assert_eq!(
+            Easing::steps(0, Jump::End).to_css(0).as_deref(),
+            Some("steps(1, jump-end)")
+        );
+        assert_eq!(
+            Easing::steps(1, Jump::None).to_css(0).as_deref(),
+            Some("steps(2, jump-none)")
+        );
+    }
+
+    #[test]
+    fn steps_jump_none_reaches_both_ends() {
+        let easing = Easing::steps(1, Jump::None);
+
+        assert!(easing.ease(0.0).abs() < f64::EPSILON);
+        assert!(easing.ease(0.49).abs() < f64::EPSILON);
+        assert!((easing.ease(0.5) - 1.0).abs() < f64::EPSILON);
+        assert!((easing.ease(1.0) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn cubic_polynomials_are_passed_as_cubic_bezier() {
+        type Named = (fn(f64) -> f64, Easing);
+
+        let named: [Named; 5] = [
+            (linear, linear.into()),
+            (quad_in, quad_in.into()),
+            (quad_out, quad_out.into()),
+            (cubic_in, cubic_in.into()),
+            (cubic_out, cubic_out.into()),
+        ];
+
+        for (function, easing) in named {
+            assert!(easing.to_css(0).unwrap().starts_with("cubic-bezier("));
+
+            for step in 0..=20 {
+                let t = f64::from(step) / 20.0;
+                assert!((easing.ease(t) - function(t)).abs() < 1e-6);
+            }
+        }
+
+        // a fn pointer might point to anything
+        let pointer: fn(f64) -> f64 = cubic_out;
+        assert!(matches!(Easing::from(pointer).0, Kind::Function(_)));
+    }
+
+    #[test]
+    fn cubic_bezier_to_css() {
+        assert_eq!(
+            Easing::cubic_bezier(-1.0, 0.5, 0.25, 2.0)
+                .to_css(0)
+                .as_deref(),
+            Some("cubic-bezier(0, 0.5, 0.25, 2)")
+        );
+    }
+}
@@ -7,13 +7,37 @@ compile_error!(
 pub mod animation;
 
 mod animate;
-pub use animate::animate;
+pub use animate::{animate, reactive, use_animate, AnimationSource, Reactive};
+
+mod animation_config;
+pub use animation_config::{
+    provide_animation_config,
+    AnimationConfig,
+    AnimationDefaults,
+    AnimationKind,
+};
+
+mod animation_mode;
+pub use animation_mode::{animation_mode, set_animation_mode, AnimationMode};
+
+mod animation_handle;
+pub use animation_handle::AnimationHandle;
 
 pub mod animations {
+    pub mod blur;
     pub mod classes;
+    pub mod crossfade;
     pub mod fade;
     pub mod flip;
+    pub mod flip3d;
+    pub mod fly;
+    pub mod keyframed;
     pub mod resize;
+    pub mod reveal;
+    pub mod rotate;
+    pub mod shared;
+    pub mod slide;
+    pub mod translate;
     pub mod zombie;
     pub mod zoom;
 
@@ -24,7 +48,28 @@ pub mod animations {
 
 pub mod easing;
 
+mod reduced_motion;
+pub use reduced_motion::{prefers_reduced_motion, provide_reduced_motion, ReducedMotion};
+
+pub mod spring;
+
+mod stagger;
+pub use stagger::{Stagger, StaggerFrom};
+
+mod time_scale;
+pub use time_scale::{set_time_scale, time_scale};
+
+mod timing_scope;
+pub use timing_scope::AnimationTiming;
+
 mod transition_duration;
 pub use transition_duration::*;
 
 pub mod utils;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use std::time::Duration;
+
+    pub use web_sys::{IntersectionObserverEntry, MutationRecord, ResizeObserverEntry};
+}
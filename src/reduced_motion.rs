@@ -0,0 +1,132 @@
+use leptos::prelude::{
+    provide_context,
+    use_context,
+    ArcReadSignal,
+    ArcRwSignal,
+    GetUntracked,
+    Set,
+};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{js_sys::WeakMap, HtmlElement, MediaQueryList};
+
+const QUERY: &str = "(prefers-reduced-motion: reduce)";
+
+/// How the built-in animations respond to the user's reduced motion
+/// preference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ReducedMotion {
+    /// Animate as usual.
+    Ignore,
+    /// Skip animations entirely - elements jump to their final state.
+    #[default]
+    Disable,
+    /// Play animations at a fraction of their duration and delay.
+    Shorten,
+    /// Replace movement with a short cross-fade.
+    Crossfade,
+}
+
+impl ReducedMotion {
+    const ALL: [Self; 4] = [Self::Ignore, Self::Disable, Self::Shorten, Self::Crossfade];
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| value.as_f64() == Some(f64::from(*policy as u8)))
+    }
+}
+
+struct Preference {
+    reduce: ArcRwSignal<bool>,
+    // kept for the listener to stay alive
+    _query: Option<(MediaQueryList, Closure<dyn Fn()>)>,
+}
+
+impl Preference {
+    fn new() -> Self {
+        let reduce = ArcRwSignal::new(false);
+
+        let query = web_sys::window()
+            .and_then(|window| window.match_media(QUERY).ok().flatten())
+            .map(|query| {
+                reduce.set(query.matches());
+
+                let closure = Closure::<dyn Fn()>::new({
+                    let reduce = reduce.clone();
+                    let query = query.clone();
+                    move || reduce.set(query.matches())
+                });
+                _ = query.add_event_listener_with_callback(
+                    "change",
+                    closure.as_ref().unchecked_ref(),
+                );
+
+                (query, closure)
+            });
+
+        Self {
+            reduce,
+            _query: query,
+        }
+    }
+}
+
+thread_local! {
+    static PREFERENCE: Preference = Preference::new();
+    // kept for as long as the element itself, as its leave animations are
+    // spawned after its owner is cleaned up
+    static POLICIES: WeakMap = WeakMap::new();
+}
+
+/// Reactive `prefers-reduced-motion: reduce` media query.
+pub fn prefers_reduced_motion() -> ArcReadSignal<bool> {
+    PREFERENCE.with(|preference| preference.reduce.read_only())
+}
+
+/// Overrides the reduced motion policy for the animations created in the
+/// current component and its descendants. [`ReducedMotion::Disable`] is used
+/// by default.
+pub fn provide_reduced_motion(policy: ReducedMotion) {
+    provide_context(policy);
+}
+
+/// Binds the policy of the closest context to the element when its animations
+/// are composed.
+pub(crate) fn register(element: &HtmlElement) {
+    let policy = use_context::<ReducedMotion>().unwrap_or_default();
+
+    POLICIES.with(|policies| {
+        policies.set(element, &JsValue::from(policy as u8));
+    });
+}
+
+/// Binds the policy of the element to its copy, e.g. a clone animated in its
+/// place.
+pub(crate) fn inherit(element: &HtmlElement, copy: &HtmlElement) {
+    POLICIES.with(|policies| {
+        policies.set(copy, &policies.get(element));
+    });
+}
+
+/// Policy to apply to the element right now - [`ReducedMotion::Ignore`] unless
+/// the user prefers reduced motion.
+pub(crate) fn policy(element: &HtmlElement) -> ReducedMotion {
+    if !PREFERENCE.with(|preference| preference.reduce.get_untracked()) {
+        return ReducedMotion::Ignore;
+    }
+
+    POLICIES
+        .with(|policies| ReducedMotion::from_js(&policies.get(element)))
+        .unwrap_or_default()
+}
+
+pub(crate) fn is_disabled(element: &HtmlElement) -> bool {
+    policy(element) == ReducedMotion::Disable
+}
+
+/// Whether the motion of the animations spawned on the element right now is
+/// replaced by a cross-fade.
+pub(crate) fn is_crossfaded(element: &HtmlElement) -> bool {
+    policy(element) == ReducedMotion::Crossfade
+}
@@ -0,0 +1,303 @@
+use std::time::Duration;
+
+use crate::easing::Easing;
+
+const STEP_SECS: f64 = 0.001;
+const MAX_SECS: f64 = 10.0;
+
+/// Damped harmonic oscillator used as a timing source in place of a fixed
+/// duration and easing.
+///
+/// The spring always travels from `0` to `1`, so the rest thresholds are
+/// relative to the animated distance. Once both the displacement and the
+/// speed fall below them, the spring is considered settled.
+///
+/// ```no_run
+/// let (duration, easing) = Spring::default().stiffness(300.0).timing(0.0);
+///
+/// spawn_animation()
+///     .element(&element)
+///     .keyframe(|t| vec![("opacity".into(), t.to_string())])
+///     .linear_keyframes(true)
+///     .duration(duration)
+///     .easing(easing)
+///     .call();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    stiffness: f64,
+    damping: f64,
+    mass: f64,
+    rest_delta: f64,
+    rest_speed: f64,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            rest_delta: 0.001,
+            rest_speed: 0.01,
+        }
+    }
+}
+
+impl Spring {
+    #[must_use]
+    pub fn new(stiffness: f64, damping: f64) -> Self {
+        Self::default().stiffness(stiffness).damping(damping)
+    }
+
+    #[must_use]
+    pub fn gentle() -> Self {
+        Self::new(120.0, 14.0)
+    }
+
+    #[must_use]
+    pub fn wobbly() -> Self {
+        Self::new(180.0, 12.0)
+    }
+
+    #[must_use]
+    pub fn stiff() -> Self {
+        Self::new(210.0, 20.0)
+    }
+
+    #[must_use]
+    pub const fn stiffness(mut self, stiffness: f64) -> Self {
+        self.stiffness = stiffness.max(f64::EPSILON);
+        self
+    }
+
+    #[must_use]
+    pub const fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    #[must_use]
+    pub const fn mass(mut self, mass: f64) -> Self {
+        self.mass = mass.max(f64::EPSILON);
+        self
+    }
+
+    /// Displacement from the target below which the spring may settle.
+    #[must_use]
+    pub const fn rest_delta(mut self, rest_delta: f64) -> Self {
+        self.rest_delta = rest_delta.abs();
+        self
+    }
+
+    /// Speed (per second) below which the spring may settle.
+    #[must_use]
+    pub const fn rest_speed(mut self, rest_speed: f64) -> Self {
+        self.rest_speed = rest_speed.abs();
+        self
+    }
+
+    /// Progress of the spring after `time` seconds, given its initial velocity
+    /// in progress per second.
+    #[must_use]
+    pub fn position(&self, velocity: f64, time: f64) -> f64 {
+        let omega = (self.stiffness / self.mass).sqrt();
+        let zeta = self.damping / (2.0 * (self.stiffness * self.mass).sqrt());
+
+        // displacement from the target
+        let x0 = -1.0;
+        let decay = (-zeta * omega * time).exp();
+
+        let displacement = if (zeta - 1.0).abs() < 1e-6 {
+            decay * (x0 + (velocity + omega * x0) * time)
+        } else if zeta < 1.0 {
+            let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+            decay
+                * (x0 * (omega_d * time).cos()
+                    + (velocity + zeta * omega * x0) / omega_d * (omega_d * time).sin())
+        } else {
+            let omega_h = omega * (zeta * zeta - 1.0).sqrt();
+            decay
+                * (x0 * (omega_h * time).cosh()
+                    + (velocity + zeta * omega * x0) / omega_h * (omega_h * time).sinh())
+        };
+
+        1.0 + displacement
+    }
+
+    /// Velocity of the spring, in progress per second, after `time` seconds.
+    #[must_use]
+    pub fn velocity(&self, velocity: f64, time: f64) -> f64 {
+        let before = (time - STEP_SECS).max(0.0);
+        let after = time + STEP_SECS;
+        (self.position(velocity, after) - self.position(velocity, before))
+            / (after - before)
+    }
+
+    fn is_settled(&self, velocity: f64, time: f64) -> bool {
+        (1.0 - self.position(velocity, time)).abs() < self.rest_delta
+            && self.velocity(velocity, time).abs() < self.rest_speed
+    }
+
+    /// Decay rate of the slowest mode of the spring along with the bounds of
+    /// its displacement and speed at the start, so that they fall below
+    /// `bound * e^(-rate * t)` over time.
+    fn envelope(&self, velocity: f64) -> (f64, f64, f64) {
+        let omega = (self.stiffness / self.mass).sqrt();
+        let zeta = self.damping / (2.0 * (self.stiffness * self.mass).sqrt());
+
+        let x0 = -1.0_f64;
+        let c = velocity + zeta * omega * x0;
+
+        if (zeta - 1.0).abs() < 1e-6 {
+            // `t * e^(-omega * t)` peaks at `1 / (e * omega)`, so half of the
+            // decay covers the linear term
+            let c = (velocity + omega * x0).abs();
+            let displacement = x0.abs() + 2.0 * c / (std::f64::consts::E * omega);
+            (omega / 2.0, displacement, c + omega * displacement)
+        } else if zeta < 1.0 {
+            let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+            let displacement = x0.hypot(c / omega_d);
+            (zeta * omega, displacement, omega * displacement)
+        } else {
+            let omega_h = omega * (zeta * zeta - 1.0).sqrt();
+            let (slow, fast) = (-zeta * omega + omega_h, -zeta * omega - omega_h);
+            let b = (velocity - slow * x0) / (fast - slow);
+            let a = x0 - b;
+            (
+                -slow,
+                a.abs() + b.abs(),
+                (a * slow).abs() + (b * fast).abs(),
+            )
+        }
+    }
+
+    /// Time it takes the spring to settle.
+    ///
+    /// The time after which the envelope of the oscillation stays within the
+    /// rest thresholds is known in closed form. The spring may settle up to
+    /// half a period earlier, which is found by stepping back frame by frame
+    /// and bisecting the last frame.
+    #[must_use]
+    pub fn settling_duration(&self, velocity: f64) -> Duration {
+        const FRAME_SECS: f64 = 1.0 / 120.0;
+
+        let (rate, displacement, speed) = self.envelope(velocity);
+        if rate <= 0.0 {
+            return Duration::from_secs_f64(MAX_SECS);
+        }
+
+        let mut time = ((displacement / self.rest_delta).ln() / rate)
+            .max((speed / self.rest_speed).ln() / rate)
+            .clamp(0.0, MAX_SECS);
+
+        while time > 0.0 && self.is_settled(velocity, (time - FRAME_SECS).max(0.0)) {
+            time = (time - FRAME_SECS).max(0.0);
+        }
+
+        let mut unsettled = (time - FRAME_SECS).max(0.0);
+        for _ in 0..8 {
+            let middle = f64::midpoint(unsettled, time);
+
+            if self.is_settled(velocity, middle) {
+                time = middle;
+            } else {
+                unsettled = middle;
+            }
+        }
+
+        Duration::from_secs_f64(time)
+    }
+
+    /// Resolves the spring into a duration and an easing that can be passed
+    /// to [`spawn_animation`](crate::utils::spawn_animation).
+    #[must_use]
+    pub fn timing(&self, velocity: f64) -> (Duration, Easing) {
+        let duration = self.settling_duration(velocity);
+        let secs = duration.as_secs_f64();
+        let spring = *self;
+
+        let easing = Easing::custom(move |t| {
+            if t >= 1.0 {
+                1.0
+            } else {
+                spring.position(velocity, t * secs)
+            }
+        });
+
+        (duration, easing)
+    }
+}
+
+/// Picks the spring if there is one, otherwise falls back to the fixed
+/// duration and easing.
+pub(crate) fn resolve_timing(
+    spring: Option<Spring>,
+    duration: Duration,
+    easing: &Easing,
+) -> (Duration, Easing) {
+    spring.map_or_else(|| (duration, easing.clone()), |spring| spring.timing(0.0))
+}
+
+/// Velocity, in progress per second, of an animation spawned with the given
+/// duration and easing, after `elapsed` time.
+pub(crate) fn eased_velocity(
+    easing: &Easing,
+    duration: Duration,
+    elapsed: Duration,
+) -> f64 {
+    let secs = duration.as_secs_f64();
+    if secs <= 0.0 {
+        return 0.0;
+    }
+
+    let t = elapsed.as_secs_f64() / secs;
+    if !(0.0..1.0).contains(&t) {
+        return 0.0;
+    }
+
+    let h = STEP_SECS / secs;
+    let before = (t - h).max(0.0);
+    let after = (t + h).min(1.0);
+
+    (easing.ease(after) - easing.ease(before)) / ((after - before) * secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settling_duration_by_stepping(spring: &Spring, velocity: f64) -> f64 {
+        let mut time = MAX_SECS;
+
+        while time > 0.0 && spring.is_settled(velocity, time - STEP_SECS) {
+            time -= STEP_SECS;
+        }
+
+        time
+    }
+
+    #[test]
+    fn settling_duration_matches_stepping() {
+        let springs = [
+            Spring::default(),
+            Spring::gentle(),
+            Spring::wobbly(),
+            Spring::stiff(),
+            Spring::new(100.0, 20.0),
+            Spring::new(100.0, 40.0).mass(2.0),
+        ];
+
+        for spring in springs {
+            for velocity in [-5.0, 0.0, 3.0, 20.0] {
+                let expected = settling_duration_by_stepping(&spring, velocity);
+                let actual = spring.settling_duration(velocity).as_secs_f64();
+
+                assert!(
+                    (expected - actual).abs() < 0.005,
+                    "{spring:?} with velocity {velocity}: {actual} instead of {expected}"
+                );
+            }
+        }
+    }
+}
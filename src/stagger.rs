@@ -0,0 +1,185 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use leptos::prelude::{queue_microtask, GetUntracked, Signal};
+use web_sys::{js_sys::Map, Element, HtmlElement};
+
+use crate::animations::zombie::ZOMBIE_ATTR;
+
+/// Where the stagger starts, i.e. which element is animated first.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StaggerFrom {
+    /// The first sibling.
+    #[default]
+    Start,
+    /// The last sibling.
+    End,
+    /// The middle sibling(s).
+    Center,
+    /// The sibling at the index, e.g. the clicked one.
+    Index(usize),
+    /// Point within the parent, in fractions of its width and height, e.g.
+    /// `(0.5, 0.5)` for its center. Intended for grids - the delay grows with
+    /// the distance from the point measured in the sizes of the element.
+    Origin(f64, f64),
+}
+
+/// Delays the animations of list items by their position among their
+/// siblings, so they run one after another instead of all at once. Apply it
+/// via [`InitializerExt::stagger`](crate::animation::InitializerExt::stagger):
+///
+/// ```no_run
+/// view! {
+///     <For each=move || items.get() key=|item| *item let:item>
+///         <div use:animate=fade::In::default()
+///             .stagger(Stagger::new(Duration::from_millis(30)).from(StaggerFrom::Center))>
+///             {item}
+///         </div>
+///     </For>
+/// }
+/// ```
+///
+/// The position is read from the DOM whenever an animation is about to run,
+/// so an item added later is delayed by its index as well. The leaving items
+/// are not counted in. Use [`limit`](Self::limit) to keep the delays of long
+/// lists reasonable.
+///
+/// The `delay` options of the built-in animations take a fixed duration, the
+/// stagger is added on top of it.
+#[must_use]
+#[derive(Clone, Copy, Debug)]
+pub struct Stagger {
+    each: Duration,
+    from: Signal<StaggerFrom>,
+    limit: Option<Duration>,
+}
+
+impl Stagger {
+    /// Delay between two consecutive siblings.
+    pub fn new(each: Duration) -> Self {
+        Self {
+            each,
+            from: StaggerFrom::default().into(),
+            limit: None,
+        }
+    }
+
+    pub fn from(mut self, from: impl Into<Signal<StaggerFrom>>) -> Self {
+        self.from = from.into();
+        self
+    }
+
+    /// Maximum delay of an element.
+    pub const fn limit(mut self, limit: Duration) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Delay of the element based on its current position.
+    pub(crate) fn delay(&self, element: &HtmlElement) -> Duration {
+        let Some(steps) = self.steps(element) else {
+            return Duration::ZERO;
+        };
+
+        let delay = self.each.mul_f64(steps);
+        self.limit.map_or(delay, |limit| delay.min(limit))
+    }
+
+    fn steps(&self, element: &HtmlElement) -> Option<f64> {
+        let parent = element.parent_element()?;
+        let siblings = siblings(&parent);
+        let index = siblings.index(element)?;
+        let last = siblings.count.saturating_sub(1);
+
+        let steps = match self.from.get_untracked() {
+            StaggerFrom::Start => index as f64,
+            StaggerFrom::End => (last - index) as f64,
+            StaggerFrom::Center => (index as f64 - last as f64 / 2.0).abs(),
+            StaggerFrom::Index(origin) => index.abs_diff(origin) as f64,
+            StaggerFrom::Origin(x, y) => distance_from_origin(element, &parent, x, y),
+        };
+
+        Some(steps)
+    }
+}
+
+/// Positions of the children of a parent, without the zombies of the leaving
+/// ones.
+struct Siblings {
+    parent: Element,
+    /// Number of all children of the parent at the time, to notice a change.
+    children_count: u32,
+    indices: Map,
+    count: usize,
+}
+
+impl Siblings {
+    fn new(parent: &Element) -> Self {
+        let children = parent.children();
+        let indices = Map::new();
+        let mut count = 0;
+
+        for child in (0..children.length()).filter_map(|idx| children.item(idx)) {
+            if !child.has_attribute(ZOMBIE_ATTR) {
+                indices.set(&child, &(count as f64).into());
+                count += 1;
+            }
+        }
+
+        Self {
+            parent: parent.clone(),
+            children_count: children.length(),
+            indices,
+            count,
+        }
+    }
+
+    fn index(&self, element: &Element) -> Option<usize> {
+        self.indices
+            .get(element)
+            .as_f64()
+            .map(|index| index as usize)
+    }
+}
+
+thread_local! {
+    /// Siblings looked up within the current task, so the items of a list
+    /// inserted at once share a single pass over their parent.
+    static SIBLINGS: RefCell<Vec<Rc<Siblings>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn siblings(parent: &Element) -> Rc<Siblings> {
+    SIBLINGS.with_borrow_mut(|cache| {
+        let children_count = parent.child_element_count();
+        if let Some(siblings) = cache
+            .iter()
+            .find(|s| &s.parent == parent && s.children_count == children_count)
+        {
+            return Rc::clone(siblings);
+        }
+
+        if cache.is_empty() {
+            queue_microtask(|| SIBLINGS.with_borrow_mut(Vec::clear));
+        }
+
+        let siblings = Rc::new(Siblings::new(parent));
+        cache.retain(|s| &s.parent != parent);
+        cache.push(Rc::clone(&siblings));
+        siblings
+    })
+}
+
+fn distance_from_origin(element: &HtmlElement, parent: &Element, x: f64, y: f64) -> f64 {
+    let rect = element.get_bounding_client_rect();
+    let parent_rect = parent.get_bounding_client_rect();
+
+    if rect.width() == 0.0 || rect.height() == 0.0 {
+        return 0.0;
+    }
+
+    let origin_x = parent_rect.width().mul_add(x, parent_rect.left());
+    let origin_y = parent_rect.height().mul_add(y, parent_rect.top());
+    let center_x = rect.width().mul_add(0.5, rect.left());
+    let center_y = rect.height().mul_add(0.5, rect.top());
+
+    ((center_x - origin_x) / rect.width()).hypot((center_y - origin_y) / rect.height())
+}
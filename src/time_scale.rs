@@ -0,0 +1,76 @@
+use std::{cell::RefCell, time::Duration};
+
+use leptos::prelude::{ArcReadSignal, ArcRwSignal, GetUntracked, Set};
+use web_sys::AnimationPlayState;
+
+const MIN_SCALE: f64 = 0.01;
+
+thread_local! {
+    static SCALE: ArcRwSignal<f64> = ArcRwSignal::new(1.0);
+    static ANIMATIONS: RefCell<Vec<web_sys::Animation>> = const {
+        RefCell::new(Vec::new())
+    };
+}
+
+/// Slows down (below `1.0`) or speeds up (above `1.0`) every animation
+/// spawned by the crate, including their delays and fixed transition
+/// durations. Animations already in flight are adjusted as well. CSS
+/// transitions of the `classes` animations are scaled once they start.
+///
+/// ```no_run
+/// let speed = RwSignal::new(1.0);
+/// Effect::new(move || set_time_scale(speed.get()));
+/// ```
+pub fn set_time_scale(scale: f64) {
+    let scale = scale.max(MIN_SCALE);
+    SCALE.with(|signal| signal.set(scale));
+
+    ANIMATIONS.with_borrow_mut(|animations| {
+        prune(animations);
+
+        // keep the direction of the animations played backwards
+        for animation in animations.iter() {
+            animation.update_playback_rate(scale.copysign(animation.playback_rate()));
+        }
+    });
+}
+
+pub fn time_scale() -> ArcReadSignal<f64> {
+    SCALE.with(ArcRwSignal::read_only)
+}
+
+fn current() -> f64 {
+    SCALE.with(GetUntracked::get_untracked)
+}
+
+fn prune(animations: &mut Vec<web_sys::Animation>) {
+    animations.retain(|animation| {
+        matches!(
+            animation.play_state(),
+            AnimationPlayState::Running | AnimationPlayState::Paused
+        )
+    });
+}
+
+/// Whether the animations are slowed down or sped up at the moment.
+pub(crate) fn is_scaled() -> bool {
+    (current() - 1.0).abs() > f64::EPSILON
+}
+
+/// Converts a duration from the animation time to the wall-clock time.
+pub(crate) fn scaled(duration: Duration) -> Duration {
+    duration.div_f64(current())
+}
+
+/// Applies the current time scale to a freshly spawned animation and keeps
+/// adjusting it until it is done.
+pub(crate) fn track(animation: &web_sys::Animation) {
+    if is_scaled() {
+        animation.set_playback_rate(current());
+    }
+
+    ANIMATIONS.with_borrow_mut(|animations| {
+        prune(animations);
+        animations.push(animation.clone());
+    });
+}
@@ -0,0 +1,202 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use leptos::prelude::queue_microtask;
+use web_sys::{Animation, HtmlElement};
+
+use crate::utils::postpone_animation;
+
+/// Timing of a single animation, see
+/// [`InitializerExt::map_timing`](crate::animation::InitializerExt::map_timing).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationTiming {
+    pub delay: Duration,
+    pub duration: Duration,
+}
+
+pub(crate) type MapTiming = Rc<dyn Fn(AnimationTiming) -> AnimationTiming>;
+
+/// Additional delay known only once the element is inserted, e.g. its stagger
+/// delay. The animations spawned before then are postponed afterwards.
+#[derive(Default)]
+struct Pending {
+    delay: Cell<Option<Duration>>,
+    spawned: RefCell<Vec<(HtmlElement, Animation)>>,
+}
+
+impl Pending {
+    fn resolve(&self, delay: Duration) {
+        self.delay.set(Some(delay));
+
+        for (element, animation) in self.spawned.take() {
+            postpone_animation(&element, &animation, delay);
+        }
+    }
+}
+
+/// Timing adjustments of the animation whose listener is currently running,
+/// set by the combinators of
+/// [`InitializerExt`](crate::animation::InitializerExt) and applied to
+/// everything spawned via [`spawn_animation`](crate::utils::spawn_animation).
+#[derive(Clone)]
+pub(crate) struct Timing {
+    delay: Duration,
+    scale: f64,
+    pending: Option<Rc<Pending>>,
+    /// Mappings of the own timing of the animations, from the outermost one.
+    maps: Vec<MapTiming>,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            delay: Duration::ZERO,
+            scale: 1.0,
+            pending: None,
+            maps: Vec::new(),
+        }
+    }
+}
+
+impl Timing {
+    /// Nests the adjustments of an inner animation within the current ones.
+    pub(crate) fn then(self, delay: Duration, scale: f64) -> Self {
+        Self {
+            delay: self.delay + delay.div_f64(self.scale),
+            scale: self.scale * scale,
+            ..self
+        }
+    }
+
+    /// Nests a mapping of the own timing of the inner animation.
+    pub(crate) fn then_mapped(mut self, map: MapTiming) -> Self {
+        self.maps.push(map);
+        self
+    }
+
+    /// Nests an additional delay resolved right after the current task, once
+    /// the element being created is inserted.
+    pub(crate) fn then_deferred(
+        self,
+        delay: impl FnOnce() -> Duration + 'static,
+    ) -> Self {
+        let pending = Rc::new(Pending::default());
+
+        queue_microtask({
+            let pending = Rc::clone(&pending);
+            let scale = self.scale;
+            move || pending.resolve(delay().div_f64(scale))
+        });
+
+        Self {
+            pending: Some(pending),
+            ..self
+        }
+    }
+
+    /// Drops the additional delay once it has been waited for.
+    pub(crate) fn without_delay(self) -> Self {
+        Self {
+            delay: Duration::ZERO,
+            pending: None,
+            ..self
+        }
+    }
+
+    /// Applies the mappings to the own timing of an animation, the innermost
+    /// one first.
+    pub(crate) fn map(&self, timing: AnimationTiming) -> AnimationTiming {
+        self.maps
+            .iter()
+            .rev()
+            .fold(timing, |timing, map| map(timing))
+    }
+
+    pub(crate) fn delay(&self, delay: Duration) -> Duration {
+        let pending = self
+            .pending
+            .as_ref()
+            .and_then(|pending| pending.delay.get())
+            .unwrap_or_default();
+
+        delay.div_f64(self.scale) + self.delay + pending
+    }
+
+    pub(crate) fn duration(&self, duration: Duration) -> Duration {
+        duration.div_f64(self.scale)
+    }
+
+    /// Keeps the animation to be postponed if it has been spawned before the
+    /// additional delay is known.
+    pub(crate) fn track(&self, element: &HtmlElement, animation: &Animation) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+
+        if pending.delay.get().is_none() {
+            pending
+                .spawned
+                .borrow_mut()
+                .push((element.clone(), animation.clone()));
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Timing> = RefCell::new(Timing::default());
+}
+
+pub(crate) fn current() -> Timing {
+    CURRENT.with_borrow(Clone::clone)
+}
+
+pub(crate) fn within<T>(timing: Timing, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.replace(timing);
+    let result = f();
+    CURRENT.set(previous);
+    result
+}
+
+/// Keeps the current adjustments for a task run later, e.g. in the next
+/// frame.
+pub(crate) fn bind(task: impl FnOnce() + 'static) -> impl FnOnce() + 'static {
+    let timing = current();
+    move || within(timing, task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_the_innermost_timing_first() {
+        let capped: MapTiming = Rc::new(|timing: AnimationTiming| AnimationTiming {
+            duration: timing.duration.min(Duration::from_millis(200)),
+            ..timing
+        });
+        let doubled: MapTiming = Rc::new(|timing: AnimationTiming| AnimationTiming {
+            duration: timing.duration * 2,
+            ..timing
+        });
+
+        let timing = Timing::default()
+            .then_mapped(doubled)
+            .then(Duration::from_millis(100), 2.0)
+            .then_mapped(capped);
+
+        let own = AnimationTiming {
+            delay: Duration::from_millis(50),
+            duration: Duration::from_millis(300),
+        };
+        assert_eq!(
+            timing.map(own),
+            AnimationTiming {
+                delay: Duration::from_millis(50),
+                duration: Duration::from_millis(400),
+            }
+        );
+    }
+}
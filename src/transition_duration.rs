@@ -5,7 +5,11 @@ use send_wrapper::SendWrapper;
 use wasm_bindgen::prelude::Closure;
 use web_sys::HtmlElement;
 
-use crate::utils::{add_oneshot_event_listener, sleep, OnAnimationsFinishedExt};
+use crate::{
+    animation_mode,
+    time_scale,
+    utils::{add_oneshot_event_listener, sleep, OnAnimationsFinishedExt},
+};
 
 type FutureCb = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Unpin>>>;
 
@@ -63,6 +67,12 @@ impl TransitionDuration {
             closure.forget();
         }
 
+        if animation_mode::is_immediate() {
+            let element = element.clone();
+            spawn_local(async move { cb(&element) });
+            return;
+        }
+
         match self {
             Self::AnimationEnd => set_closure(element, "animationend", cb),
             Self::TransitionEnd => {
@@ -72,7 +82,7 @@ impl TransitionDuration {
                 let element = element.clone();
                 let duration = *duration;
                 spawn_local(async move {
-                    sleep(duration).await;
+                    sleep(time_scale::scaled(duration)).await;
                     cb(&element);
                 });
             }
@@ -8,14 +8,32 @@ mod trackable;
 pub use trackable::Trackable;
 
 mod on_animations_finished_ext;
-pub use on_animations_finished_ext::OnAnimationsFinishedExt;
+pub use on_animations_finished_ext::{
+    all_finished,
+    Cancelled,
+    Completed,
+    OnAnimationsFinishedExt,
+};
+
+mod layout;
+pub use layout::{measure, mutate};
+
+mod lifecycle_callback;
+pub use lifecycle_callback::LifecycleCallback;
 
 mod options;
-pub(crate) use options::define_options;
+pub(crate) use options::{config_default, define_options};
+
+mod progress;
+pub(crate) use progress::eased_progress;
 
 mod spawn_animation;
+pub(crate) use spawn_animation::postpone_animation;
 pub use spawn_animation::spawn_animation;
 
+mod timeline;
+pub use timeline::{Step, Timeline};
+
 mod log_error;
 pub(crate) use log_error::log_error;
 
@@ -0,0 +1,73 @@
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+};
+
+use leptos::prelude::request_animation_frame;
+
+use crate::timing_scope;
+
+type Task = Box<dyn FnOnce()>;
+
+#[derive(Default)]
+struct Queue {
+    reads: RefCell<Vec<Task>>,
+    writes: RefCell<Vec<Task>>,
+    scheduled: Cell<bool>,
+}
+
+thread_local! {
+    static QUEUE: Queue = Queue::default();
+}
+
+fn flush() {
+    loop {
+        let reads = QUEUE.with(|queue| mem::take(&mut *queue.reads.borrow_mut()));
+        let has_reads = !reads.is_empty();
+
+        for task in reads {
+            task();
+        }
+
+        // includes the writes scheduled by the reads above
+        let writes = QUEUE.with(|queue| mem::take(&mut *queue.writes.borrow_mut()));
+
+        if !has_reads && writes.is_empty() {
+            break;
+        }
+
+        for task in writes {
+            task();
+        }
+    }
+
+    QUEUE.with(|queue| queue.scheduled.set(false));
+}
+
+fn schedule() {
+    if QUEUE.with(|queue| queue.scheduled.replace(true)) {
+        return;
+    }
+
+    request_animation_frame(flush);
+}
+
+/// Schedules a layout read, e.g. `getBoundingClientRect`, for the next frame.
+///
+/// All reads scheduled for a frame run before any write scheduled via
+/// [`mutate`], so the browser has to compute the layout only once instead of
+/// after every interleaved style change. Both phases run before the frame is
+/// rendered.
+pub fn measure(task: impl FnOnce() + 'static) {
+    let task = timing_scope::bind(task);
+    QUEUE.with(|queue| queue.reads.borrow_mut().push(Box::new(task)));
+    schedule();
+}
+
+/// Schedules a DOM or style write, e.g. spawning an animation, for the next
+/// frame. See [`measure`].
+pub fn mutate(task: impl FnOnce() + 'static) {
+    let task = timing_scope::bind(task);
+    QUEUE.with(|queue| queue.writes.borrow_mut().push(Box::new(task)));
+    schedule();
+}
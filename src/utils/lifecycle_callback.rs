@@ -0,0 +1,28 @@
+use std::rc::Rc;
+
+use send_wrapper::SendWrapper;
+use web_sys::HtmlElement;
+
+type Cb = SendWrapper<Rc<dyn Fn(&HtmlElement)>>;
+
+/// Optional callback receiving the animated element at a specific point of an
+/// animation's lifecycle, e.g. once the entering animation is over.
+#[derive(Clone, Default)]
+pub struct LifecycleCallback(Option<Cb>);
+
+impl LifecycleCallback {
+    pub fn call(&self, element: &HtmlElement) {
+        if let Some(cb) = &self.0 {
+            cb(element);
+        }
+    }
+}
+
+impl<F> From<F> for LifecycleCallback
+where
+    F: Fn(&HtmlElement) + 'static,
+{
+    fn from(cb: F) -> Self {
+        Self(Some(SendWrapper::new(Rc::new(cb))))
+    }
+}
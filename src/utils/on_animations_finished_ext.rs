@@ -1,11 +1,31 @@
+use std::future::Future;
+
+use futures::future::try_join_all;
 use leptos::task::spawn_local;
 use wasm_bindgen::{prelude::Closure, JsCast};
+use wasm_bindgen_futures::JsFuture;
 use web_sys::{GetAnimationsOptions, HtmlElement};
 
 use super::{add_oneshot_event_listener, animation_frame};
 
+/// Marks animations that have played until the end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Completed;
+
+/// Marks animations of which at least one has been cancelled before reaching
+/// the end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
 pub trait OnAnimationsFinishedExt {
     fn on_animations_finished(&self, cb: impl Fn() + 'static, subtree: bool);
+
+    /// Resolves once all animations currently present on the element are
+    /// finished.
+    fn animations_finished(
+        &self,
+        subtree: bool,
+    ) -> impl Future<Output = Result<Completed, Cancelled>> + 'static;
 }
 
 fn get_animations(element: &HtmlElement, subtree: bool) -> Vec<web_sys::Animation> {
@@ -23,6 +43,22 @@ fn get_animations(element: &HtmlElement, subtree: bool) -> Vec<web_sys::Animatio
         .collect()
 }
 
+/// Resolves once all given animations are finished.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] as soon as any of the animations gets cancelled.
+pub async fn all_finished(
+    animations: Vec<web_sys::Animation>,
+) -> Result<Completed, Cancelled> {
+    try_join_all(animations.iter().map(|animation| async move {
+        let promise = animation.finished().map_err(|_| Cancelled)?;
+        JsFuture::from(promise).await.map_err(|_| Cancelled)
+    }))
+    .await
+    .map(|_| Completed)
+}
+
 impl OnAnimationsFinishedExt for HtmlElement {
     fn on_animations_finished(&self, cb: impl Fn() + 'static, subtree: bool) {
         let closure = Closure::<dyn Fn()>::new(cb);
@@ -45,4 +81,11 @@ impl OnAnimationsFinishedExt for HtmlElement {
             }
         });
     }
+
+    fn animations_finished(
+        &self,
+        subtree: bool,
+    ) -> impl Future<Output = Result<Completed, Cancelled>> + 'static {
+        all_finished(get_animations(self, subtree))
+    }
 }
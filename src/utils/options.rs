@@ -1,9 +1,56 @@
 macro_rules! define_options {
     (
         $struct:ident . $options_field:ident ;
+        @with_config $kind:ident ;
+        $($rest:tt)*
+    ) => {
+        $crate::utils::define_options!(
+            @define [$struct] [] [Some($crate::AnimationKind::$kind)] $options_field;
+            $($rest)*
+        );
+
+        impl Default for Options {
+            fn default() -> Self {
+                Self::with_defaults()
+            }
+        }
+    };
+    (
+        $struct:ident . $options_field:ident ;
+        $($rest:tt)*
+    ) => {
+        $crate::utils::define_options!(
+            @define [$struct] [] [None] $options_field;
+            $($rest)*
+        );
+
+        impl Default for Options {
+            fn default() -> Self {
+                Self::with_defaults()
+            }
+        }
+    };
+    // The defaults may depend on the type parameter, so `Options` is created
+    // via `Options::with_defaults::<P>()` instead of `Default`.
+    (
+        $struct:ident < $param:ident : $bound:path > . $options_field:ident ;
+        @with_config $kind:expr ;
+        $($rest:tt)*
+    ) => {
+        $crate::utils::define_options!(
+            @define [$struct<$param>] [$param: $bound] [Some($kind)] $options_field;
+            $($rest)*
+        );
+    };
+    (
+        @define [$($struct:tt)*] [$($generics:tt)*] [$config:expr] $options_field:ident ;
         $(
             $field_name:ident : $field_type:ty = $field_default:expr ,
         )*
+        $(
+            @with_callbacks
+            $($callback_name:ident),+ ;
+        )?
         $(
             @with_setters
             $(
@@ -16,6 +63,11 @@ macro_rules! define_options {
             $(
                 $field_name: $field_type,
             )*
+            $(
+                $(
+                    $callback_name: $crate::utils::LifecycleCallback,
+                )+
+            )?
             $(
                 $(
                     $auto_field_name: $auto_field_type,
@@ -23,22 +75,43 @@ macro_rules! define_options {
             )?
         }
 
-        impl Default for Options {
-            fn default() -> Self {
+        impl Options {
+            fn with_defaults<$($generics)*>() -> Self {
+                let config = $crate::animation_config::defaults($config);
+
                 Self {
                     $(
                         $field_name: $field_default,
                     )*
                     $(
                         $(
-                            $auto_field_name: $auto_field_default,
+                            $callback_name: $crate::utils::LifecycleCallback::default(),
+                        )+
+                    )?
+                    $(
+                        $(
+                            $auto_field_name: $crate::utils::config_default!(
+                                config.$auto_field_name,
+                                $auto_field_default
+                            ),
                         )*
                     )?
                 }
             }
         }
 
-        impl $struct {
+        impl<$($generics)*> $($struct)* {
+            $($(
+                pub fn $callback_name(
+                    self,
+                    callback: impl Fn(&web_sys::HtmlElement) + 'static
+                ) -> Self {
+                    use leptos::prelude::Set;
+                    self.$options_field.clone().$callback_name().set(callback.into());
+                    self
+                }
+            )+)?
+
             paste::paste! {
                 $($(
                     pub fn $auto_field_name(self, value: impl Into< $auto_field_type >) -> Self {
@@ -66,4 +139,24 @@ macro_rules! define_options {
     };
 }
 
+/// Picks the value from the animation config for the fields it covers.
+macro_rules! config_default {
+    ($config:ident.duration, $default:expr) => {
+        $config.duration.map_or_else(|| $default, Into::into)
+    };
+    ($config:ident.easing, $default:expr) => {
+        $config.easing.clone().unwrap_or_else(|| $default)
+    };
+    ($config:ident.delay, $default:expr) => {
+        $config.delay.unwrap_or($default)
+    };
+    ($config:ident.enabled, $default:expr) => {
+        $config.enabled.unwrap_or($default)
+    };
+    ($config:ident. $field:ident, $default:expr) => {
+        $default
+    };
+}
+
+pub(crate) use config_default;
 pub(crate) use define_options;
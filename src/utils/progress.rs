@@ -0,0 +1,27 @@
+use web_sys::{Animation, AnimationPlayState};
+
+use crate::easing::Easing;
+
+/// Eased progress of an animation spawned by
+/// [`spawn_animation`](super::spawn_animation) with the given easing, i.e. how
+/// far its keyframes are applied at the moment. A delayed animation is held at
+/// its start until then. Returns `None` once the animation is over.
+pub(crate) fn eased_progress(animation: &Animation, easing: &Easing) -> Option<f64> {
+    if matches!(
+        animation.play_state(),
+        AnimationPlayState::Idle | AnimationPlayState::Finished
+    ) {
+        return None;
+    }
+
+    let timing = animation.effect()?.get_computed_timing();
+
+    let elapsed = timing.get_local_time().unwrap_or_default()
+        - timing.get_delay().unwrap_or_default();
+    let progress = match timing.get_active_duration() {
+        Some(duration) if duration > 0.0 => (elapsed / duration).clamp(0.0, 1.0),
+        _ => 1.0,
+    };
+
+    Some(easing.ease(progress))
+}
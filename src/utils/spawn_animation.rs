@@ -1,74 +1,209 @@
 use std::time::Duration;
 
 use bon::builder;
-use leptos::task::spawn_local;
 use web_sys::{
     js_sys::{Array, Object, Reflect},
     Animation,
-    AnimationPlayState,
+    FillMode,
     HtmlElement,
     KeyframeAnimationOptions,
+    OptionalEffectTiming,
 };
 
-use super::sleep;
-use crate::easing::Easing;
+use crate::{
+    animation_handle,
+    animation_mode,
+    easing::Easing,
+    reduced_motion::{self, ReducedMotion},
+    time_scale,
+    timing_scope,
+    AnimationTiming,
+};
 
 const KEYFRAME_INTERVAL_MS: f64 = 10.0;
+const SHORTENED_RATIO: f64 = 0.25;
+const CROSSFADE_DURATION: Duration = Duration::from_millis(150);
+const MOTION_PROPERTIES: [&str; 4] = ["transform", "translate", "scale", "rotate"];
+
+type Keyframe = Vec<(String, String)>;
+
+fn without_motion(keyframe: Keyframe) -> Keyframe {
+    keyframe
+        .into_iter()
+        .filter(|(property, _)| !MOTION_PROPERTIES.contains(&property.as_str()))
+        .collect()
+}
+
+fn keyframe_object(keyframe: Keyframe) -> Object {
+    let object = Object::new();
+
+    for (property, value) in keyframe {
+        Reflect::set(&object, &property.into(), &value.into()).unwrap();
+    }
+
+    object
+}
 
+/// Animates the element with keyframes generated from the eased progress.
+///
+/// By default, a keyframe is generated every few milliseconds. If `keyframe`
+/// changes the properties linearly with the progress, set `linear_keyframes`
+/// to let the browser interpolate between `keyframe(0.0)` and `keyframe(1.0)`
+/// instead, as long as the easing can be expressed in CSS (`cubic-bezier()`,
+/// `steps()`, or `linear()` if supported by the browser).
+///
+/// The [`ReducedMotion`] policy of the element is honoured when the user
+/// prefers reduced motion. In the cross-fade mode, `crossfade` keyframes are
+/// played instead of the regular ones. If not provided, the regular keyframes
+/// are played without the `transform`, `translate`, `scale` and `rotate`
+/// properties.
+///
+/// Both `delay` and `offset` are a part of the returned animation - pausing or
+/// seeking it covers them as well, and unless `hold` is unset, the element is
+/// held at `keyframe(0.0)` until then. With `hold_end` set, the element is held
+/// at `keyframe(1.0)` once the animation is over as well, until its fill is
+/// updated. `offset` places the animation within a
+/// longer sequence, see [`Timeline`](super::Timeline), so unlike `delay` it is
+/// kept in the cross-fade mode, along with the duration of the animation.
 #[builder]
 pub fn spawn_animation(
     element: &HtmlElement,
-    keyframe: impl Fn(f64) -> Vec<(String, String)>,
+    keyframe: impl Fn(f64) -> Keyframe,
     duration: Duration,
-    easing: Easing,
+    easing: impl Into<Easing>,
     #[builder(default)] delay: Duration,
+    offset: Option<Duration>,
+    #[builder(default = true)] hold: bool,
+    #[builder(default)] hold_end: bool,
+    #[builder(default)] linear_keyframes: bool,
+    crossfade: Option<&dyn Fn(f64) -> Keyframe>,
 ) -> Animation {
+    let timing = timing_scope::current();
+    let AnimationTiming { delay, duration } =
+        timing.map(AnimationTiming { delay, duration });
+    let sequenced = offset.is_some();
+    let (duration, delay, offset) = (
+        timing.duration(duration),
+        timing.delay(delay),
+        timing.duration(offset.unwrap_or_default()),
+    );
+
+    let motionless = |t| without_motion(keyframe(t));
+    let (keyframe, duration, delay, offset): (&dyn Fn(f64) -> Keyframe, _, _, _) =
+        match reduced_motion::policy(element) {
+            _ if animation_mode::is_immediate() => {
+                (&keyframe, Duration::ZERO, Duration::ZERO, Duration::ZERO)
+            }
+            ReducedMotion::Ignore => (&keyframe, duration, delay, offset),
+            ReducedMotion::Disable => {
+                (&keyframe, Duration::ZERO, Duration::ZERO, Duration::ZERO)
+            }
+            ReducedMotion::Shorten => (
+                &keyframe,
+                duration.mul_f64(SHORTENED_RATIO),
+                delay.mul_f64(SHORTENED_RATIO),
+                offset.mul_f64(SHORTENED_RATIO),
+            ),
+            // keep the timing of the sequenced animations
+            ReducedMotion::Crossfade => (
+                crossfade.unwrap_or(&motionless),
+                if sequenced {
+                    duration
+                } else {
+                    duration.min(CROSSFADE_DURATION)
+                },
+                Duration::ZERO,
+                offset,
+            ),
+        };
+
+    let easing = easing.into();
     let duration = duration.as_millis() as f64;
     let keyframes = Array::new();
 
     let num_steps =
         (duration.max(KEYFRAME_INTERVAL_MS) / KEYFRAME_INTERVAL_MS).ceil() as usize;
 
-    for step in 0..=num_steps {
-        let keyframe = keyframe(easing(step as f64 / num_steps as f64));
-
-        let object = Object::new();
+    let options = KeyframeAnimationOptions::new();
+    options.set_duration(duration);
 
-        for (property, value) in keyframe {
-            Reflect::set(&object, &property.into(), &value.into()).unwrap();
-        }
+    let delay = delay + offset;
+    if !delay.is_zero() {
+        options.set_delay(delay.as_secs_f64() * 1000.0);
+    }
 
-        keyframes.push(&object);
+    match (hold && !delay.is_zero(), hold_end) {
+        (true, true) => options.set_fill(FillMode::Both),
+        (true, false) => options.set_fill(FillMode::Backwards),
+        (false, true) => options.set_fill(FillMode::Forwards),
+        (false, false) => {}
     }
 
-    let options = KeyframeAnimationOptions::new();
-    options.set_duration(duration);
+    let native_easing = if linear_keyframes {
+        easing.to_css(num_steps)
+    } else {
+        None
+    };
+
+    if let Some(native_easing) = native_easing {
+        options.set_easing(&native_easing);
+        keyframes.push(&keyframe_object(keyframe(0.0)));
+        keyframes.push(&keyframe_object(keyframe(1.0)));
+    } else {
+        for step in 0..=num_steps {
+            let keyframe = keyframe(easing.ease(step as f64 / num_steps as f64));
+            keyframes.push(&keyframe_object(keyframe));
+        }
+    }
 
     let animation =
         element.animate_with_keyframe_animation_options(Some(&keyframes), &options);
 
-    if delay.is_zero() {
-        return animation;
-    }
+    time_scale::track(&animation);
+    animation_handle::track(element, &animation);
+    timing.track(element, &animation);
 
-    _ = animation.pause();
-
-    spawn_local({
-        let animation = animation.clone();
-        let element = element.clone();
-        async move {
-            sleep(delay).await;
+    animation
+}
 
-            if !element.is_connected()
-                // check if the animation has already been cancelled
-                || animation.play_state() == AnimationPlayState::Idle
-            {
-                return;
-            }
+/// Delays an animation already spawned by [`spawn_animation`] even further,
+/// e.g. by a delay known only once the element has been inserted. The element
+/// is held at `keyframe(0.0)` in the meantime.
+pub(crate) fn postpone_animation(
+    element: &HtmlElement,
+    animation: &Animation,
+    delay: Duration,
+) {
+    let delay = match reduced_motion::policy(element) {
+        _ if animation_mode::is_immediate() => return,
+        ReducedMotion::Ignore => delay,
+        ReducedMotion::Shorten => delay.mul_f64(SHORTENED_RATIO),
+        ReducedMotion::Disable | ReducedMotion::Crossfade => return,
+    };
+
+    let Some(effect) = animation.effect() else {
+        return;
+    };
 
-            _ = animation.play();
-        }
-    });
+    if delay.is_zero() {
+        return;
+    }
 
-    animation
+    let computed = effect.get_computed_timing();
+    let timing = OptionalEffectTiming::new();
+    timing.set_delay(
+        computed.get_delay().unwrap_or_default() + delay.as_secs_f64() * 1000.0,
+    );
+    // keep holding the end state, if it is
+    timing.set_fill(
+        if matches!(
+            computed.get_fill(),
+            Some(FillMode::Forwards | FillMode::Both)
+        ) {
+            FillMode::Both
+        } else {
+            FillMode::Backwards
+        },
+    );
+    _ = effect.update_timing_with_timing(&timing);
 }
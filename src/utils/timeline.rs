@@ -0,0 +1,220 @@
+use std::{collections::HashMap, time::Duration};
+
+use leptos::task::spawn_local;
+use web_sys::{FillMode, HtmlElement, OptionalEffectTiming};
+
+use super::{all_finished, log_error, spawn_animation};
+use crate::{
+    easing::{cubic_out, Easing},
+    AnimationHandle,
+};
+
+type Keyframe = Vec<(String, String)>;
+
+/// Single animation of a [`Timeline`], mirroring the arguments of
+/// [`spawn_animation`].
+#[must_use]
+pub struct Step {
+    element: HtmlElement,
+    keyframe: Box<dyn Fn(f64) -> Keyframe>,
+    duration: Duration,
+    easing: Easing,
+    linear_keyframes: bool,
+    crossfade: Option<Box<dyn Fn(f64) -> Keyframe>>,
+    offset: Duration,
+    overlap: Duration,
+}
+
+impl Step {
+    pub fn new(
+        element: &HtmlElement,
+        keyframe: impl Fn(f64) -> Keyframe + 'static,
+    ) -> Self {
+        Self {
+            element: element.clone(),
+            keyframe: Box::new(keyframe),
+            duration: Duration::from_millis(200),
+            easing: cubic_out.into(),
+            linear_keyframes: false,
+            crossfade: None,
+            offset: Duration::ZERO,
+            overlap: Duration::ZERO,
+        }
+    }
+
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn easing(mut self, easing: impl Into<Easing>) -> Self {
+        self.easing = easing.into();
+        self
+    }
+
+    pub const fn linear_keyframes(mut self, linear_keyframes: bool) -> Self {
+        self.linear_keyframes = linear_keyframes;
+        self
+    }
+
+    pub fn crossfade(mut self, crossfade: impl Fn(f64) -> Keyframe + 'static) -> Self {
+        self.crossfade = Some(Box::new(crossfade));
+        self
+    }
+
+    /// Starts the step later than its position in the timeline.
+    pub const fn offset(mut self, offset: Duration) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Starts the step earlier than its position in the timeline, e.g. to let
+    /// it overlap with the preceding one.
+    pub const fn overlap(mut self, overlap: Duration) -> Self {
+        self.overlap = overlap;
+        self
+    }
+}
+
+/// Choreographs animations of one or more elements, e.g. "fade in the card,
+/// then slide in its title while pulsing the button":
+///
+/// ```no_run
+/// let handle = Timeline::new()
+///     .then(Step::new(&card, fade_in).duration(Duration::from_millis(300)))
+///     .label("content")
+///     .then(Step::new(&title, slide_in).easing(easing::cubic_out))
+///     .with(Step::new(&subtitle, slide_in).offset(Duration::from_millis(50)))
+///     .at(
+///         "content",
+///         Step::new(&button, pulse).duration(Duration::from_millis(600)),
+///     )
+///     .play();
+///
+/// spawn_local(async move {
+///     if handle.finished().await.is_ok() {
+///         // next step of the tour
+///     }
+/// });
+/// ```
+///
+/// Every step is spawned right away with its start time as an offset, so the
+/// returned [`AnimationHandle`] pauses, seeks or cancels the whole timeline at
+/// once. Until its start, a step holds the element at `keyframe(0.0)` only if
+/// no earlier step animates any of the same properties of the element, so it
+/// does not override them in the meantime. Once over, a step holds the element
+/// at `keyframe(1.0)` until the whole timeline is finished.
+#[must_use]
+#[derive(Default)]
+pub struct Timeline {
+    steps: Vec<(Step, Duration)>,
+    labels: HashMap<String, Duration>,
+    last_start: Duration,
+    end: Duration,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the step once every step added so far is finished.
+    pub fn then(self, step: Step) -> Self {
+        let start = self.end;
+        self.add(step, start)
+    }
+
+    /// Starts the step along with the previously added one.
+    pub fn with(self, step: Step) -> Self {
+        let start = self.last_start;
+        self.add(step, start)
+    }
+
+    /// Marks the current end of the timeline, see [`at`](Self::at).
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.insert(label.into(), self.end);
+        self
+    }
+
+    /// Starts the step at the label. Unknown labels point to the end of the
+    /// timeline.
+    pub fn at(self, label: &str, step: Step) -> Self {
+        let start = self.labels.get(label).copied().unwrap_or_else(|| {
+            log_error!("Timeline label \"{label}\" not found");
+            self.end
+        });
+
+        self.add(step, start)
+    }
+
+    fn add(mut self, step: Step, start: Duration) -> Self {
+        let start = (start + step.offset).saturating_sub(step.overlap);
+
+        self.last_start = start;
+        self.end = self.end.max(start + step.duration);
+        self.steps.push((step, start));
+        self
+    }
+
+    /// Total duration of the timeline, before the
+    /// [time scale](crate::set_time_scale) and
+    /// [`ReducedMotion::Shorten`](crate::ReducedMotion::Shorten) are applied.
+    pub const fn duration(&self) -> Duration {
+        self.end
+    }
+
+    /// Spawns every step.
+    pub fn play(mut self) -> AnimationHandle {
+        self.steps.sort_by_key(|(_, start)| *start);
+
+        let mut animated: Vec<(HtmlElement, String)> = Vec::new();
+
+        let animations = self
+            .steps
+            .into_iter()
+            .map(|(step, start)| {
+                let mut hold = true;
+
+                for (property, _) in (step.keyframe)(0.0) {
+                    let animated_property = (step.element.clone(), property);
+
+                    if animated.contains(&animated_property) {
+                        hold = false;
+                    } else {
+                        animated.push(animated_property);
+                    }
+                }
+
+                spawn_animation()
+                    .element(&step.element)
+                    .keyframe(step.keyframe)
+                    .duration(step.duration)
+                    .easing(step.easing)
+                    .offset(start)
+                    .hold(hold)
+                    .hold_end(true)
+                    .linear_keyframes(step.linear_keyframes)
+                    .maybe_crossfade(step.crossfade.as_deref())
+                    .call()
+            })
+            .collect::<Vec<_>>();
+
+        // the end states are released at once, like a single animation would
+        spawn_local({
+            let animations = animations.clone();
+            async move {
+                if all_finished(animations.clone()).await.is_ok() {
+                    let timing = OptionalEffectTiming::new();
+                    timing.set_fill(FillMode::None);
+
+                    for effect in animations.iter().filter_map(web_sys::Animation::effect)
+                    {
+                        _ = effect.update_timing_with_timing(&timing);
+                    }
+                }
+            }
+        });
+
+        animations.into_iter().collect()
+    }
+}